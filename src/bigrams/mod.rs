@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::language::Language;
+
+static DUTCH: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/nl.txt")));
+static ENGLISH: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/en.txt")));
+static FRENCH: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/fr.txt")));
+static GERMAN: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/de.txt")));
+static ITALIAN: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/it.txt")));
+static PORTUGUESE: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/pt.txt")));
+static SPANISH: LazyLock<BigramTable> =
+    LazyLock::new(|| BigramTable::parse(include_str!("../data/bigrams/es.txt")));
+
+/// BigramTable lists the letter pairs that occur in a language, with their counts.
+/// Pairs missing from the table are considered illegal, e.g. "xq" in English.
+#[derive(Debug, Clone, Default)]
+pub struct BigramTable {
+    counts: HashMap<(char, char), u32>,
+}
+
+impl BigramTable {
+    /// Parses a table with one `<bigram> <count>` entry per line. Lines starting
+    /// with '#' and blank lines are skipped, a missing count defaults to 1.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The table source
+    ///
+    /// # Returns
+    ///
+    /// - `BigramTable` - The parsed table
+    ///
+    pub fn parse(s: &str) -> Self {
+        let mut counts: HashMap<(char, char), u32> = HashMap::new();
+
+        for line in s.lines() {
+            let line: &str = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let bigram: Vec<char> = fields.next().unwrap_or_default().chars().collect();
+            let count: u32 = fields.next().and_then(|c| c.parse().ok()).unwrap_or(1);
+
+            if let [a, b] = bigram[..] {
+                counts.insert((a, b), count);
+            }
+        }
+
+        BigramTable { counts }
+    }
+
+    /// The bundled English table
+    pub fn english() -> &'static BigramTable {
+        &ENGLISH
+    }
+
+    /// The bundled table for a language
+    pub fn for_language(language: Language) -> &'static BigramTable {
+        match language {
+            Language::Dutch => &DUTCH,
+            Language::English | Language::Auto => &ENGLISH,
+            Language::French => &FRENCH,
+            Language::German => &GERMAN,
+            Language::Italian => &ITALIAN,
+            Language::Portuguese => &PORTUGUESE,
+            Language::Spanish => &SPANISH,
+        }
+    }
+
+    /// How often the pair was seen, 0 for illegal pairs
+    pub fn count(&self, a: char, b: char) -> u32 {
+        self.counts.get(&(a, b)).copied().unwrap_or(0)
+    }
+
+    /// Whether or not the pair occurs in the language
+    pub fn is_legal(&self, a: char, b: char) -> bool {
+        self.counts.contains_key(&(a, b))
+    }
+
+    /// Fraction of adjacent letter pairs in a word that are illegal. The word is
+    /// lowercased and pairs involving non-letters are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The word
+    ///
+    /// # Returns
+    ///
+    /// - `f64` - 0.0 - 1.0, 0.0 when the word has no letter pairs
+    ///
+    pub fn illegal_fraction(&self, word: &str) -> f64 {
+        let pairs: Vec<(char, char)> = letter_pairs(word);
+        if pairs.is_empty() {
+            return 0.0;
+        }
+
+        let illegal: usize = pairs.iter().filter(|(a, b)| !self.is_legal(*a, *b)).count();
+        illegal as f64 / pairs.len() as f64
+    }
+
+    /// Scores a word's plausibility as (illegal pairs, summed log count of the legal
+    /// pairs). Fewer illegal pairs and a higher sum read more naturally.
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The word
+    ///
+    /// # Returns
+    ///
+    /// - `(usize, f64)` - The number of illegal pairs and the legal pairs' log count
+    ///
+    pub fn plausibility(&self, word: &str) -> (usize, f64) {
+        let mut illegal: usize = 0;
+        let mut log_count: f64 = 0.0;
+
+        for (a, b) in letter_pairs(word) {
+            match self.count(a, b) {
+                0 => illegal += 1,
+                n => log_count += (n as f64).ln(),
+            }
+        }
+
+        (illegal, log_count)
+    }
+}
+
+/// Lowercased pairs of adjacent letters, composed to NFC first, e.g. "Don't" => [('d', 'o'), ('o', 'n')]
+fn letter_pairs(word: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = word.nfc().flat_map(char::to_lowercase).collect();
+
+    chars
+        .windows(2)
+        .filter(|w| w[0].is_alphabetic() && w[1].is_alphabetic())
+        .map(|w| (w[0], w[1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let table = BigramTable::parse("# comment\nth 10\n\nhe\nbad entry 3\n");
+        assert_eq!(table.count('t', 'h'), 10);
+        assert_eq!(table.count('h', 'e'), 1);
+        assert_eq!(table.count('x', 'q'), 0);
+        assert!(!table.is_legal('b', 'a'));
+    }
+
+    #[test]
+    fn test_english() {
+        let table = BigramTable::english();
+        assert!(table.is_legal('t', 'h'));
+        assert!(!table.is_legal('x', 'q'));
+
+        assert_eq!(table.illegal_fraction("Hello"), 0.0);
+        assert_eq!(table.illegal_fraction("a"), 0.0);
+        assert!(table.illegal_fraction("hxqlo") > 0.0);
+    }
+
+    #[test]
+    fn test_languages() {
+        assert!(BigramTable::for_language(Language::German).is_legal('s', 'c'));
+        assert!(BigramTable::for_language(Language::Spanish).is_legal('ñ', 'o'));
+        assert!(BigramTable::for_language(Language::French).is_legal('ç', 'a'));
+        assert!(!BigramTable::for_language(Language::English).is_legal('ç', 'a'));
+        assert!(BigramTable::for_language(Language::Dutch).is_legal('i', 'j'));
+        assert!(BigramTable::for_language(Language::Portuguese).is_legal('ã', 'o'));
+        assert!(BigramTable::for_language(Language::Italian).is_legal('z', 'z'));
+    }
+
+    #[test]
+    fn test_plausibility() {
+        let table = BigramTable::english();
+        let (illegal, log_count) = table.plausibility("there");
+        assert_eq!(illegal, 0);
+        assert!(log_count > 0.0);
+
+        assert_eq!(table.plausibility("txqe").0, 3);
+
+        // NFD input reads the same as NFC
+        let spanish = BigramTable::for_language(Language::Spanish);
+        assert_eq!(
+            spanish.plausibility("nin\u{303}o"),
+            spanish.plausibility("niño")
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use crate::language::Language;
+
+/// Number of trigrams in each language profile, also the largest rank difference
+/// a single trigram can add to the distance
+const PROFILE_SIZE: usize = 300;
+
+static PROFILES: LazyLock<Vec<(Language, Vec<[char; 3]>)>> = LazyLock::new(|| {
+    [
+        (Language::Dutch, include_str!("../data/trigrams/nl.txt")),
+        (Language::English, include_str!("../data/trigrams/en.txt")),
+        (Language::French, include_str!("../data/trigrams/fr.txt")),
+        (Language::German, include_str!("../data/trigrams/de.txt")),
+        (Language::Italian, include_str!("../data/trigrams/it.txt")),
+        (
+            Language::Portuguese,
+            include_str!("../data/trigrams/pt.txt"),
+        ),
+        (Language::Spanish, include_str!("../data/trigrams/es.txt")),
+    ]
+    .into_iter()
+    .map(|(language, source)| (language, parse_profile(source)))
+    .collect()
+});
+
+/// How much text Language::Auto looks at when picking a profile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DetectionScope {
+    /// One language for the whole input (default)
+    #[default]
+    Document,
+    /// One language per paragraph, for mixed-language input
+    Paragraph,
+}
+
+/// Identifies the language of a text offline by comparing its most frequent letter
+/// trigrams with the bundled profiles, using the out-of-place measure of Cavnar and
+/// Trenkle, e.g. "Le chat est sur la table" => French
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The text, ideally a sentence or longer
+///
+/// # Returns
+///
+/// - `Option<Language>` - The closest bundled language, None for text without letters
+///
+pub fn detect_language(s: &str) -> Option<Language> {
+    let ranks: HashMap<[char; 3], usize> = text_trigrams(s);
+    if ranks.is_empty() {
+        return None;
+    }
+
+    PROFILES
+        .iter()
+        .map(|(language, profile)| {
+            let distance: usize = profile
+                .iter()
+                .enumerate()
+                .map(|(i, t)| ranks.get(t).map_or(PROFILE_SIZE, |&r| r.abs_diff(i)))
+                .sum();
+            (distance, *language)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, language)| language)
+}
+
+/// Ranks the lowercase letter trigrams of a text, most frequent first. Non-letters
+/// count as word boundaries, written as spaces.
+fn text_trigrams(s: &str) -> HashMap<[char; 3], usize> {
+    let chars: Vec<char> = [' ']
+        .into_iter()
+        .chain(
+            s.chars()
+                .flat_map(char::to_lowercase)
+                .map(|c| if c.is_alphabetic() { c } else { ' ' }),
+        )
+        .chain([' '])
+        .collect();
+
+    let mut counts: HashMap<[char; 3], usize> = HashMap::new();
+    for w in chars.windows(3) {
+        // skip trigrams made of a lone boundary between two others
+        if w[1] == ' ' && (w[0] == ' ' || w[2] == ' ') {
+            continue;
+        }
+        *counts.entry([w[0], w[1], w[2]]).or_insert(0) += 1;
+    }
+
+    let mut sorted: Vec<([char; 3], usize)> = counts.into_iter().collect();
+    sorted.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    sorted
+        .into_iter()
+        .take(PROFILE_SIZE * 2)
+        .enumerate()
+        .map(|(rank, (trigram, _))| (trigram, rank))
+        .collect()
+}
+
+/// Parses a profile with one trigram per line, '_' marking word boundaries
+fn parse_profile(s: &str) -> Vec<[char; 3]> {
+    s.lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| {
+            let chars: Vec<char> = l.chars().map(|c| if c == '_' { ' ' } else { c }).collect();
+            chars.try_into().ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles() {
+        assert_eq!(PROFILES.len(), Language::ALL.len());
+        assert!(PROFILES.iter().all(|(_, p)| p.len() == PROFILE_SIZE));
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let samples = [
+            (
+                "The quick brown fox jumps over the lazy dog",
+                Language::English,
+            ),
+            (
+                "Le renard brun saute par-dessus le chien paresseux",
+                Language::French,
+            ),
+            (
+                "Der schnelle braune Fuchs springt über den faulen Hund",
+                Language::German,
+            ),
+            (
+                "El rápido zorro marrón salta sobre el perro perezoso",
+                Language::Spanish,
+            ),
+            (
+                "La volpe veloce salta sopra il cane pigro che dorme",
+                Language::Italian,
+            ),
+            (
+                "De snelle bruine vos springt over de luie hond heen",
+                Language::Dutch,
+            ),
+            (
+                "A raposa marrom rápida pula sobre o cão preguiçoso",
+                Language::Portuguese,
+            ),
+        ];
+
+        for (text, language) in samples {
+            assert_eq!(detect_language(text), Some(language), "{text}");
+        }
+        assert_eq!(detect_language("1234 !?"), None);
+    }
+}
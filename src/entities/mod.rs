@@ -0,0 +1,129 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::language::Language;
+
+/// Multi-word names kept readable wherever they occur, e.g. "New York"
+static MULTIWORD: LazyLock<Vec<Vec<&'static str>>> = LazyLock::new(|| {
+    include_str!("../data/entities/multiword.txt")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(pieces)
+        .collect()
+});
+
+/// Splits text into its UAX #29 words and punctuation, whitespace aside, e.g.
+/// "St. Louis" => ["St", ".", "Louis"]
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The text
+///
+/// # Returns
+///
+/// - `Vec<&str>` - The pieces, in order
+///
+fn pieces(s: &str) -> Vec<&str> {
+    s.split_word_bounds()
+        .filter(|piece| !piece.trim().is_empty())
+        .collect()
+}
+
+/// Finds the named entities of a text: known multi-word names, entries of the
+/// gazetteer and, unless the language capitalizes its nouns, capitalized words that
+/// do not start a sentence. Sentences are found with UAX #29 sentence segmentation.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The text
+/// - `gazetteer` (`&[String]`) - Names supplied by the user, single or multi-word
+/// - `languages` (`&[(usize, Language)]`) - Each language block's starting byte offset and language
+///
+/// # Returns
+///
+/// - `Vec<Range<usize>>` - The byte ranges of the entities
+///
+pub(crate) fn find_entities(
+    s: &str,
+    gazetteer: &[String],
+    languages: &[(usize, Language)],
+) -> Vec<Range<usize>> {
+    let text: Vec<(usize, &str)> = s
+        .split_word_bound_indices()
+        .filter(|(_, piece)| !piece.trim().is_empty())
+        .collect();
+    let custom: Vec<Vec<&str>> = gazetteer.iter().map(|entry| pieces(entry)).collect();
+
+    let mut entities: Vec<Range<usize>> = Vec::new();
+    for start in 0..text.len() {
+        for entry in MULTIWORD.iter().chain(&custom) {
+            let end: usize = start + entry.len();
+            if !entry.is_empty()
+                && end <= text.len()
+                && text[start..end].iter().map(|(_, p)| p).eq(entry.iter())
+            {
+                let (last, piece) = text[end - 1];
+                entities.push(text[start].0..last + piece.len());
+            }
+        }
+    }
+
+    let sentence_starts: Vec<usize> = s.split_sentence_bound_indices().map(|(i, _)| i).collect();
+    let mut sentence: usize = 0;
+    let mut first: bool = true;
+    for (index, word) in s.split_word_bound_indices() {
+        while sentence + 1 < sentence_starts.len() && sentence_starts[sentence + 1] <= index {
+            sentence += 1;
+            first = true;
+        }
+        if !word.chars().any(char::is_alphanumeric) {
+            continue;
+        }
+
+        let language: Language = languages
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= index)
+            .map_or(Language::English, |(_, language)| *language);
+        if !first && !language.capitalizes_nouns() && word.starts_with(char::is_uppercase) {
+            entities.push(index..index + word.len());
+        }
+        first = false;
+    }
+
+    entities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entities<'a>(s: &'a str, gazetteer: &[String], language: Language) -> Vec<&'a str> {
+        let mut ranges: Vec<Range<usize>> = find_entities(s, gazetteer, &[(0, language)]);
+        ranges.sort_by_key(|r| r.start);
+        ranges.into_iter().map(|r| &s[r]).collect()
+    }
+
+    #[test]
+    fn test_find_entities() {
+        let s = "New York is big. We met Alice there, then flew to St. Louis.";
+        assert_eq!(
+            entities(s, &[], Language::English),
+            ["New York", "York", "Alice", "St. Louis", "St"]
+        );
+
+        // neither the sentence start nor German nouns are taken for names
+        let s = "Der Hund schläft in Berlin.";
+        assert!(entities(s, &[], Language::German).is_empty());
+
+        let gazetteer = ["acme widgets".to_string(), "Zorblax".to_string()];
+        let s = "Buy acme widgets. Zorblax agrees.";
+        assert_eq!(
+            entities(s, &gazetteer, Language::English),
+            ["acme widgets", "Zorblax"]
+        );
+    }
+}
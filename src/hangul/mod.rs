@@ -0,0 +1,82 @@
+/// First precomposed Hangul syllable, 가
+const S_BASE: u32 = 0xAC00;
+
+/// Number of initial consonants
+const L_COUNT: u32 = 19;
+
+/// Number of vowels
+const V_COUNT: u32 = 21;
+
+/// Number of final consonants, plus one for none
+const T_COUNT: u32 = 28;
+
+/// Syllable is a precomposed Hangul block split into its jamo, each an index into the
+/// Unicode tables of initial consonants, vowels and final consonants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Syllable {
+    /// Initial consonant, 0 - 18
+    pub lead: u32,
+    /// Vowel, 0 - 20
+    pub vowel: u32,
+    /// Final consonant, 1 - 27, or 0 when the block has none
+    pub tail: u32,
+}
+
+impl Syllable {
+    /// Splits a precomposed Hangul syllable into its jamo, e.g. '한' => ㅎ ㅏ ㄴ
+    ///
+    /// # Arguments
+    ///
+    /// - `c` (`char`) - The character
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Syllable>` - The jamo, None if the character isn't a Hangul syllable
+    ///
+    pub(crate) fn decompose(c: char) -> Option<Syllable> {
+        let index: u32 = (c as u32).checked_sub(S_BASE)?;
+        if index >= L_COUNT * V_COUNT * T_COUNT {
+            return None;
+        }
+
+        Some(Syllable {
+            lead: index / (V_COUNT * T_COUNT),
+            vowel: index % (V_COUNT * T_COUNT) / T_COUNT,
+            tail: index % T_COUNT,
+        })
+    }
+
+    /// The jamo at a position: 0 for the initial consonant, 1 for the vowel, 2 for the
+    /// final consonant
+    pub(crate) fn jamo_mut(&mut self, position: usize) -> &mut u32 {
+        match position {
+            0 => &mut self.lead,
+            1 => &mut self.vowel,
+            _ => &mut self.tail,
+        }
+    }
+
+    /// Recomposes the jamo into a precomposed Hangul syllable
+    pub(crate) fn compose(&self) -> char {
+        let code: u32 = S_BASE + (self.lead * V_COUNT + self.vowel) * T_COUNT + self.tail;
+        char::from_u32(code).unwrap_or('\u{FFFD}')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let han = Syllable::decompose('한').unwrap();
+        assert_eq!((han.lead, han.vowel, han.tail), (18, 0, 4));
+        assert_eq!(Syllable::decompose('어').unwrap().tail, 0);
+        assert_eq!(Syllable::decompose('a'), None);
+        assert_eq!(Syllable::decompose('ㅎ'), None);
+
+        for c in ['가', '각', '힣', '어', '국'] {
+            assert_eq!(Syllable::decompose(c).unwrap().compose(), c);
+        }
+    }
+}
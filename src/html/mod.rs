@@ -0,0 +1,481 @@
+use std::ops::Range;
+
+use crate::markup::{Tag, Unit, collect_units, decode_numeric, read_tag, scramble_runs};
+
+/// Elements whose text is left alone by default
+const SKIPPED: &[&str] = &[
+    "script", "style", "code", "pre", "head", "template", "textarea",
+];
+
+/// Elements holding raw text, where nothing but their own end tag is markup
+const RAW_TEXT: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Inline elements, which a word may run through, e.g. "w<b>or</b>d"
+const INLINE: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "big", "cite", "data", "del", "dfn", "em", "font", "i", "ins",
+    "kbd", "label", "mark", "q", "s", "samp", "small", "span", "strike", "strong", "sub", "sup",
+    "time", "tt", "u", "var", "wbr",
+];
+
+/// Void elements, which have no end tag
+const VOID: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Named character references decoded besides the Latin-1 ones
+const NAMED: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("hellip", '…'),
+    ("lsquo", '‘'),
+    ("rsquo", '’'),
+    ("ldquo", '“'),
+    ("rdquo", '”'),
+    ("OElig", 'Œ'),
+    ("oelig", 'œ'),
+    ("Scaron", 'Š'),
+    ("scaron", 'š'),
+];
+
+/// Names of the Latin-1 character references, U+00A0 to U+00FF in order
+const LATIN_1: [&str; 96] = [
+    "nbsp", "iexcl", "cent", "pound", "curren", "yen", "brvbar", "sect", "uml", "copy", "ordf",
+    "laquo", "not", "shy", "reg", "macr", "deg", "plusmn", "sup2", "sup3", "acute", "micro",
+    "para", "middot", "cedil", "sup1", "ordm", "raquo", "frac14", "frac12", "frac34", "iquest",
+    "Agrave", "Aacute", "Acirc", "Atilde", "Auml", "Aring", "AElig", "Ccedil", "Egrave", "Eacute",
+    "Ecirc", "Euml", "Igrave", "Iacute", "Icirc", "Iuml", "ETH", "Ntilde", "Ograve", "Oacute",
+    "Ocirc", "Otilde", "Ouml", "times", "Oslash", "Ugrave", "Uacute", "Ucirc", "Uuml", "Yacute",
+    "THORN", "szlig", "agrave", "aacute", "acirc", "atilde", "auml", "aring", "aelig", "ccedil",
+    "egrave", "eacute", "ecirc", "euml", "igrave", "iacute", "icirc", "iuml", "eth", "ntilde",
+    "ograve", "oacute", "ocirc", "otilde", "ouml", "divide", "oslash", "ugrave", "uacute", "ucirc",
+    "uuml", "yacute", "thorn", "yuml",
+];
+
+/// HtmlOptions chooses what typoglycemia_html_with() scrambles besides the visible
+/// text, e.g.
+///
+/// ```
+/// use typoglycemia::HtmlOptions;
+///
+/// let options = HtmlOptions::new()
+///     .with_skipped_elements(["script", "style", "kbd"])
+///     .with_attributes(["title", "alt"])
+///     .with_entities(true);
+///
+/// assert_eq!(options.attributes(), ["title", "alt"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlOptions {
+    skipped: Vec<String>,
+    attributes: Vec<String>,
+    entities: bool,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            skipped: SKIPPED.iter().map(|e| e.to_string()).collect(),
+            attributes: Vec::new(),
+            entities: false,
+        }
+    }
+}
+
+impl HtmlOptions {
+    /// Returns options that skip script, style, code, pre, head, template and textarea
+    /// elements, attributes and words containing character references
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the elements whose text is left alone, matched case-insensitively
+    ///
+    /// # Arguments
+    ///
+    /// - `elements` (`I`) - The element names, e.g. ["script", "style", "kbd"]
+    ///
+    pub fn with_skipped_elements<I, S>(mut self, elements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.skipped = elements
+            .into_iter()
+            .map(|e| e.as_ref().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Scrambles the values of these attributes as well, e.g. "title" or "alt"
+    ///
+    /// # Arguments
+    ///
+    /// - `attributes` (`I`) - The attribute names, matched case-insensitively
+    ///
+    pub fn with_attributes<I, S>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.attributes = attributes
+            .into_iter()
+            .map(|a| a.as_ref().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Decodes character references so that words containing them are scrambled too,
+    /// e.g. "caf&eacute;s" => "cfa&eacute;s". Each reference is written back as it was,
+    /// wherever it moves. Otherwise words containing them are copied as they were.
+    ///
+    /// # Arguments
+    ///
+    /// - `decode` (`bool`) - Whether or not to scramble through character references
+    ///
+    pub fn with_entities(mut self, decode: bool) -> Self {
+        self.entities = decode;
+        self
+    }
+
+    /// The elements whose text is left alone
+    pub fn skipped_elements(&self) -> &[String] {
+        &self.skipped
+    }
+
+    /// The attributes whose values are scrambled
+    pub fn attributes(&self) -> &[String] {
+        &self.attributes
+    }
+
+    /// Whether words containing character references are scrambled
+    pub fn entities(&self) -> bool {
+        self.entities
+    }
+}
+
+/// A piece of an HTML document
+#[derive(Debug)]
+enum Token {
+    /// Character data
+    Text(Range<usize>),
+    /// A start or end tag
+    Tag(Tag),
+    /// A comment, doctype, CDATA section or processing instruction
+    Markup,
+}
+
+/// Splits an HTML document into text, tags and other markup. The content of raw text
+/// elements such as script and style is a single text token.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+///
+/// # Returns
+///
+/// - `Vec<Token>` - The tokens, in order
+///
+fn tokenize(s: &str) -> Vec<Token> {
+    let bytes: &[u8] = s.as_bytes();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut text_start: usize = 0;
+    let mut i: usize = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+
+        let rest: &str = &s[i..];
+        let closing = |marker: &str, from: usize| -> usize {
+            s[from..]
+                .find(marker)
+                .map_or(s.len(), |p| from + p + marker.len())
+        };
+        let next: Option<u8> = bytes.get(i + 1).copied();
+        let after: usize;
+        let mut token: Token = Token::Markup;
+        if rest.starts_with("<!--") {
+            after = closing("-->", i + 4);
+        } else if rest.starts_with("<![CDATA[") {
+            after = closing("]]>", i + 9);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            after = closing(">", i + 2);
+        } else if next.is_some_and(|c| c.is_ascii_alphabetic())
+            || (next == Some(b'/') && bytes.get(i + 2).is_some_and(u8::is_ascii_alphabetic))
+        {
+            let (mut tag, end) = read_tag(s, i);
+            tag.name.make_ascii_lowercase();
+            for (attribute, _) in &mut tag.attributes {
+                attribute.make_ascii_lowercase();
+            }
+            after = end;
+            token = Token::Tag(tag);
+        } else {
+            i += 1;
+            continue;
+        }
+
+        if text_start < i {
+            tokens.push(Token::Text(text_start..i));
+        }
+        i = after;
+        text_start = after;
+
+        // raw text runs up to the element's own end tag
+        if let Token::Tag(tag) = &token {
+            if !tag.end && !tag.self_closing && RAW_TEXT.contains(&tag.name.as_str()) {
+                let end_tag: String = format!("</{}", tag.name);
+                let end: usize = s[i..]
+                    .to_ascii_lowercase()
+                    .find(&end_tag)
+                    .map_or(s.len(), |p| i + p);
+                tokens.push(token);
+                if i < end {
+                    tokens.push(Token::Text(i..end));
+                }
+                i = end;
+                text_start = end;
+                continue;
+            }
+        }
+        tokens.push(token);
+    }
+
+    if text_start < s.len() {
+        tokens.push(Token::Text(text_start..s.len()));
+    }
+
+    tokens
+}
+
+/// Decodes a character reference, e.g. "&eacute;" or "&#233;" => 'é'
+///
+/// # Arguments
+///
+/// - `reference` (`&str`) - The reference, "&" and ";" included
+///
+/// # Returns
+///
+/// - `Option<char>` - The character, None for references it does not know
+///
+fn decode(reference: &str) -> Option<char> {
+    let name: &str = reference.strip_prefix('&')?.strip_suffix(';')?;
+    if name.starts_with('#') {
+        return decode_numeric(name);
+    }
+
+    NAMED
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, c)| *c)
+        .or_else(|| {
+            LATIN_1
+                .iter()
+                .position(|n| *n == name)
+                .and_then(|p| char::from_u32(0xA0 + p as u32))
+        })
+}
+
+/// Scrambles the visible text of an HTML document, and the chosen attribute values,
+/// leaving every other byte as it was. Text nodes separated only by inline tags are
+/// scrambled together, e.g. "w<b>or</b>d" => "w<b>ro</b>d".
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `options` (`&HtmlOptions`) - What to skip and what else to scramble
+/// - `scramble` (`F`) - Scrambles plain text
+///
+/// # Returns
+///
+/// - `String` - The document with its text scrambled
+///
+pub(crate) fn scramble_html<F: FnMut(&str) -> String>(
+    s: &str,
+    options: &HtmlOptions,
+    scramble: F,
+) -> String {
+    let mut runs: Vec<Vec<Unit>> = Vec::new();
+    let mut run: Vec<Unit> = Vec::new();
+    let mut open: Vec<String> = Vec::new();
+    // without entities every reference is kept as a unit, so that its word can be left out
+    let decoder: fn(&str) -> Option<char> = match options.entities {
+        true => decode,
+        false => |_| Some(char::REPLACEMENT_CHARACTER),
+    };
+
+    for token in tokenize(s) {
+        match token {
+            Token::Text(node) if open.is_empty() => {
+                collect_units(s, node, decoder, &mut run, &mut runs)
+            }
+            Token::Text(_) => {}
+            Token::Markup => runs.push(std::mem::take(&mut run)),
+            Token::Tag(tag) => {
+                if !INLINE.contains(&tag.name.as_str()) {
+                    runs.push(std::mem::take(&mut run));
+                }
+                if open.is_empty() {
+                    for (attribute, value) in &tag.attributes {
+                        if options.attributes.contains(attribute) {
+                            let mut units: Vec<Unit> = Vec::new();
+                            collect_units(s, value.clone(), decoder, &mut units, &mut runs);
+                            runs.push(units);
+                        }
+                    }
+                }
+
+                if !options.skipped.contains(&tag.name) {
+                    continue;
+                }
+                if tag.end {
+                    if let Some(p) = open.iter().rposition(|name| *name == tag.name) {
+                        open.remove(p);
+                    }
+                } else if !tag.self_closing && !VOID.contains(&tag.name.as_str()) {
+                    open.push(tag.name);
+                }
+            }
+        }
+    }
+    runs.push(run);
+    if !options.entities {
+        runs = runs
+            .into_iter()
+            .flat_map(|run| without_referenced_words(s, run))
+            .collect();
+    }
+
+    scramble_runs(s, &runs, scramble)
+}
+
+/// Takes the words containing a character reference out of a run, so that they are
+/// copied as they were, e.g. "caf&eacute;s" and "surprisingly&nbsp;intact"
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `run` (`Vec<Unit>`) - The run
+///
+/// # Returns
+///
+/// - `Vec<Vec<Unit>>` - The runs left around the words taken out
+///
+fn without_referenced_words(s: &str, run: Vec<Unit>) -> Vec<Vec<Unit>> {
+    let mut runs: Vec<Vec<Unit>> = Vec::new();
+    let mut kept: Vec<Unit> = Vec::new();
+    let mut word: Vec<Unit> = Vec::new();
+
+    let mut end_word = |word: &mut Vec<Unit>, kept: &mut Vec<Unit>| {
+        // a reference is the only unit not written as its text
+        if word.iter().any(|u| s[u.source.clone()] != u.text) {
+            runs.push(std::mem::take(kept));
+            word.clear();
+        } else {
+            kept.append(word);
+        }
+    };
+
+    for unit in run {
+        if s[unit.source.clone()].chars().all(char::is_whitespace) {
+            end_word(&mut word, &mut kept);
+            kept.push(unit);
+        } else {
+            word.push(unit);
+        }
+    }
+    end_word(&mut word, &mut kept);
+    runs.push(kept);
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    #[test]
+    fn test_tokenize() {
+        let s = "<!DOCTYPE html><p class=\"x\" title='A b'>Hi <b>there</b><br/>\
+                 <script>if (a < b) {}</script><!-- note --></p>";
+        let tokens: Vec<Token> = tokenize(s);
+        let texts: Vec<&str> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Text(r) => Some(&s[r.clone()]),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(texts, ["Hi ", "there", "if (a < b) {}"]);
+
+        let Token::Tag(p) = &tokens[1] else {
+            panic!("{:?}", tokens[1]);
+        };
+        assert_eq!(p.name, "p");
+        let attributes: Vec<(&str, &str)> = p
+            .attributes
+            .iter()
+            .map(|(a, r)| (a.as_str(), &s[r.clone()]))
+            .collect();
+        assert_eq!(attributes, [("class", "x"), ("title", "A b")]);
+        assert!(matches!(&tokens[6], Token::Tag(br) if br.name == "br" && br.self_closing));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("&eacute;"), Some('é'));
+        assert_eq!(decode("&#233;"), Some('é'));
+        assert_eq!(decode("&#xE9;"), Some('é'));
+        assert_eq!(decode("&amp;"), Some('&'));
+        assert_eq!(decode("&nbsp;"), Some('\u{A0}'));
+        assert_eq!(decode("&yuml;"), Some('ÿ'));
+        assert_eq!(decode("&bogus;"), None);
+    }
+
+    #[test]
+    fn test_scramble_html() {
+        let reverse = |t: &str| -> String {
+            t.split(' ')
+                .map(|w| w.graphemes(true).rev().collect::<String>())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        let s = "<p title=\"tool tip\">w<b>or</b>d <code>code</code> caf&eacute;s</p>";
+        assert_eq!(
+            scramble_html(s, &HtmlOptions::new(), reverse),
+            "<p title=\"tool tip\">d<b>ro</b>w <code>code</code> caf&eacute;s</p>"
+        );
+        assert_eq!(
+            scramble_html(
+                "<p>w<b>or</b>d&amp;ing ab&#1;cd ef</p>",
+                &HtmlOptions::new(),
+                reverse
+            ),
+            "<p>w<b>or</b>d&amp;ing ab&#1;cd fe</p>"
+        );
+        let options = HtmlOptions::new()
+            .with_attributes(["TITLE"])
+            .with_entities(true);
+        assert_eq!(
+            scramble_html(s, &options, reverse),
+            "<p title=\"loot pit\">d<b>ro</b>w <code>code</code> s&eacute;fac</p>"
+        );
+
+        // block elements end words, skipped elements nest
+        let s = "<div>ab</div><div>cd</div><pre>ef<b>gh</b>ij</pre>kl";
+        assert_eq!(
+            scramble_html(s, &HtmlOptions::new(), reverse),
+            "<div>ba</div><div>dc</div><pre>ef<b>gh</b>ij</pre>lk"
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::language::Language;
+
+static DUTCH: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/nl.pat")).with_bounds(2, 2)
+});
+static ENGLISH: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/en.pat")).with_bounds(2, 3)
+});
+static FRENCH: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/fr.pat")).with_bounds(2, 2)
+});
+static GERMAN: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/de.pat")).with_bounds(2, 2)
+});
+static ITALIAN: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/it.pat")).with_bounds(2, 2)
+});
+static PORTUGUESE: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/pt.pat")).with_bounds(2, 3)
+});
+static SPANISH: LazyLock<Hyphenator> = LazyLock::new(|| {
+    Hyphenator::parse(include_str!("../data/hyphenation/es.pat")).with_bounds(2, 2)
+});
+
+/// Hyphenator splits words into syllables with Liang's algorithm, the one used by
+/// TeX, e.g. "hyphenation" => ["hy", "phen", "ation"]
+#[derive(Debug, Clone)]
+pub struct Hyphenator {
+    patterns: HashMap<String, Vec<u8>>,
+    exceptions: HashMap<String, Vec<usize>>,
+    max_pattern_len: usize,
+    left_min: usize,
+    right_min: usize,
+}
+
+impl Default for Hyphenator {
+    fn default() -> Self {
+        Hyphenator {
+            patterns: HashMap::new(),
+            exceptions: HashMap::new(),
+            max_pattern_len: 0,
+            left_min: 2,
+            right_min: 2,
+        }
+    }
+}
+
+impl Hyphenator {
+    /// Parses TeX hyphenation patterns. Accepts bare whitespace-separated patterns as
+    /// well as `\patterns{...}` and `\hyphenation{...}` groups, the latter holding
+    /// exceptions such as "as-so-ciate". Everything after a '%' is a comment.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The pattern source
+    ///
+    /// # Returns
+    ///
+    /// - `Hyphenator` - The hyphenator, breaking no closer than two letters to either end
+    ///
+    pub fn parse(s: &str) -> Self {
+        let mut hyphenator: Hyphenator = Hyphenator::default();
+        let mut in_exceptions: bool = false;
+
+        for line in s.lines() {
+            let line: &str = line.split('%').next().unwrap_or_default();
+
+            for token in line.split_whitespace() {
+                if token.starts_with("\\hyphenation") {
+                    in_exceptions = true;
+                } else if token.starts_with("\\patterns") || token.starts_with('}') {
+                    in_exceptions = false;
+                }
+
+                let token: &str = token
+                    .trim_start_matches("\\hyphenation")
+                    .trim_start_matches("\\patterns")
+                    .trim_matches(|c| c == '{' || c == '}');
+                if token.is_empty() || token.starts_with('\\') {
+                    continue;
+                }
+
+                if in_exceptions {
+                    hyphenator.add_exception(token);
+                } else {
+                    hyphenator.add_pattern(token);
+                }
+            }
+        }
+
+        hyphenator
+    }
+
+    /// Reads and parses a TeX pattern file, see parse()
+    ///
+    /// # Arguments
+    ///
+    /// - `path` (`P`) - Path to the pattern file, e.g. "hyph-sv.tex"
+    ///
+    /// # Returns
+    ///
+    /// - `io::Result<Hyphenator>` - The hyphenator, or the error raised reading the file
+    ///
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    /// The bundled patterns for a language
+    pub fn for_language(language: Language) -> &'static Hyphenator {
+        match language {
+            Language::Dutch => &DUTCH,
+            Language::English | Language::Auto => &ENGLISH,
+            Language::French => &FRENCH,
+            Language::German => &GERMAN,
+            Language::Italian => &ITALIAN,
+            Language::Portuguese => &PORTUGUESE,
+            Language::Spanish => &SPANISH,
+        }
+    }
+
+    /// Sets the minimum number of letters kept before the first and after the last break
+    ///
+    /// # Arguments
+    ///
+    /// - `left_min` (`usize`) - Letters before the first break, at least 1
+    /// - `right_min` (`usize`) - Letters after the last break, at least 1
+    ///
+    pub fn with_bounds(mut self, left_min: usize, right_min: usize) -> Self {
+        self.left_min = left_min.max(1);
+        self.right_min = right_min.max(1);
+        self
+    }
+
+    /// Records a pattern such as "1tion" or ".ex5", digits giving the break level
+    fn add_pattern(&mut self, pattern: &str) {
+        let mut letters: String = String::new();
+        let mut levels: Vec<u8> = vec![0];
+
+        for c in pattern.chars() {
+            match c.to_digit(10) {
+                Some(level) => *levels.last_mut().unwrap() = level as u8,
+                None => {
+                    letters.extend(c.to_lowercase());
+                    levels.push(0);
+                }
+            }
+        }
+
+        if !letters.is_empty() {
+            self.max_pattern_len = self.max_pattern_len.max(levels.len() - 1);
+            self.patterns.insert(letters, levels);
+        }
+    }
+
+    /// Records an exception such as "ta-ble", hyphens marking the breaks
+    fn add_exception(&mut self, exception: &str) {
+        let mut word: String = String::new();
+        let mut breaks: Vec<usize> = Vec::new();
+
+        for c in exception.chars() {
+            if c == '-' {
+                breaks.push(word.chars().count());
+            } else {
+                word.extend(c.to_lowercase());
+            }
+        }
+
+        self.exceptions.insert(word, breaks);
+    }
+
+    /// Finds the hyphenation points of a word
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - A single word without surrounding punctuation
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<usize>` - Byte offsets of the breaks, in order, always on grapheme boundaries
+    ///
+    pub fn breaks(&self, word: &str) -> Vec<usize> {
+        // patterns are NFC, so each grapheme is composed first; starts[p] holds the
+        // byte offset of chars[p] when it begins a grapheme of the input
+        let mut chars: Vec<char> = Vec::new();
+        let mut starts: Vec<Option<usize>> = Vec::new();
+        for (offset, grapheme) in word.grapheme_indices(true) {
+            for (k, c) in grapheme.nfc().enumerate() {
+                chars.push(c.to_lowercase().next().unwrap_or(c));
+                starts.push((k == 0).then_some(offset));
+            }
+        }
+        let n: usize = chars.len();
+        if n < self.left_min + self.right_min {
+            return Vec::new();
+        }
+
+        let positions: Vec<usize> = match self.exceptions.get(&chars.iter().collect::<String>()) {
+            Some(breaks) => breaks.clone(),
+            None => {
+                let dotted: Vec<char> = [&['.'], &chars[..], &['.']].concat();
+                let mut levels: Vec<u8> = vec![0; dotted.len() + 1];
+
+                for i in 0..dotted.len() {
+                    let mut key: String = String::new();
+                    for &c in dotted[i..].iter().take(self.max_pattern_len) {
+                        key.push(c);
+                        if let Some(pattern) = self.patterns.get(&key) {
+                            for (k, &level) in pattern.iter().enumerate() {
+                                levels[i + k] = levels[i + k].max(level);
+                            }
+                        }
+                    }
+                }
+
+                // levels[p] sits before dotted[p], i.e. before chars[p - 1]
+                (1..n).filter(|&p| levels[p + 1] % 2 == 1).collect()
+            }
+        };
+
+        positions
+            .into_iter()
+            .filter(|&p| p >= self.left_min && p + self.right_min <= n)
+            .filter_map(|p| starts[p])
+            .collect()
+    }
+
+    /// Splits a word into its syllables
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - A single word without surrounding punctuation
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<&str>` - The syllables, a single one when there are no breaks
+    ///
+    pub fn syllables<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        let mut syllables: Vec<&str> = Vec::new();
+        let mut start: usize = 0;
+
+        for b in self.breaks(word) {
+            syllables.push(&word[start..b]);
+            start = b;
+        }
+        syllables.push(&word[start..]);
+
+        syllables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let h = Hyphenator::parse("% comment\n\\patterns{\n1ba\n}\n\\hyphenation{\nta-ble\n}\n");
+        assert_eq!(h.syllables("abab"), vec!["abab"]);
+
+        let h = h.with_bounds(1, 1);
+        assert_eq!(h.syllables("ababa"), vec!["a", "ba", "ba"]);
+        assert_eq!(h.syllables("Table"), vec!["Ta", "ble"]);
+        assert_eq!(h.breaks("ébaba"), vec![2, 4]);
+        assert_eq!(h.breaks("e\u{301}baba"), vec![3, 5]);
+    }
+
+    #[test]
+    fn test_bundled() {
+        let english = Hyphenator::for_language(Language::English);
+        assert_eq!(
+            english.syllables("hyphenation"),
+            vec!["hy", "phen", "ation"]
+        );
+        assert_eq!(english.syllables("Extensive"), vec!["Ex", "ten", "sive"]);
+        assert_eq!(english.syllables("cat"), vec!["cat"]);
+
+        let german = Hyphenator::for_language(Language::German);
+        assert_eq!(german.syllables("Silbentrennung").len(), 4);
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use crate::bigrams::BigramTable;
+use crate::hyphenation::Hyphenator;
+
+/// The typewriter apostrophe and the typographic one, U+2019, which the elisions,
+/// clitics and stopwords match alike although they are written with the former
+pub(crate) const APOSTROPHES: &[char] = &['\'', '\u{2019}'];
+
+static STOPWORDS: LazyLock<[HashSet<&'static str>; 7]> = LazyLock::new(|| {
+    [
+        include_str!("../data/stopwords/nl.txt"),
+        include_str!("../data/stopwords/en.txt"),
+        include_str!("../data/stopwords/fr.txt"),
+        include_str!("../data/stopwords/de.txt"),
+        include_str!("../data/stopwords/it.txt"),
+        include_str!("../data/stopwords/pt.txt"),
+        include_str!("../data/stopwords/es.txt"),
+    ]
+    .map(parse_word_list)
+});
+
+/// Language selects the locale-specific profile used while scrambling: digraphs,
+/// affixes, elisions and clitics, stopwords, leet substitutions, bigrams and
+/// hyphenation patterns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// Dutch
+    Dutch,
+    /// English (default)
+    #[default]
+    English,
+    /// French
+    French,
+    /// German
+    German,
+    /// Italian
+    Italian,
+    /// Portuguese
+    Portuguese,
+    /// Spanish
+    Spanish,
+    /// Detects the language of each document or paragraph, see DetectionScope.
+    /// Anywhere else, e.g. in the metrics, it uses the English profile.
+    Auto,
+}
+
+impl Language {
+    /// Every bundled language
+    pub const ALL: [Language; 7] = [
+        Language::Dutch,
+        Language::English,
+        Language::French,
+        Language::German,
+        Language::Italian,
+        Language::Portuguese,
+        Language::Spanish,
+    ];
+
+    /// ISO 639-1 code, e.g. "en", or "auto"
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::Dutch => "nl",
+            Language::English => "en",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Spanish => "es",
+            Language::Auto => "auto",
+        }
+    }
+
+    /// Looks a language up by its ISO 639-1 code, case-insensitively
+    ///
+    /// # Arguments
+    ///
+    /// - `code` (`&str`) - The code, e.g. "de"
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Language>` - The language, None if it isn't bundled
+    ///
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL
+            .into_iter()
+            .chain([Language::Auto])
+            .find(|l| l.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Letter clusters kept together while shuffling, longest first, e.g. "sch" in German
+    pub fn clusters(&self) -> &'static [&'static str] {
+        match self {
+            Language::Dutch => &["sch", "ch", "ei", "eu", "ie", "ij", "oe", "ou", "ui"],
+            Language::English | Language::Auto => {
+                &["tch", "ch", "ck", "ng", "ph", "qu", "sh", "th", "wh"]
+            }
+            Language::French => &["eau", "ch", "gn", "ou", "ph", "qu"],
+            Language::German => &["tsch", "sch", "ch", "ck", "ph", "qu"],
+            Language::Italian => &["gli", "sch", "ch", "gh", "gn", "qu", "sc"],
+            Language::Portuguese => &["ch", "gu", "lh", "nh", "qu", "rr", "ss"],
+            Language::Spanish => &["ch", "gu", "ll", "qu", "rr"],
+        }
+    }
+
+    /// Common prefixes kept intact as part of the first anchor, e.g. "un" in "unlikely"
+    pub fn prefixes(&self) -> &'static [&'static str] {
+        match self {
+            Language::Dutch => &["onder", "over", "ont", "ver", "her", "be", "ge"],
+            Language::English | Language::Auto => &[
+                "under", "inter", "trans", "over", "anti", "dis", "mis", "non", "pre", "sub", "un",
+                "re",
+            ],
+            Language::French => &["contre", "entre", "anti", "sur", "dés", "pré", "dé", "re"],
+            Language::German => &[
+                "zusammen", "unter", "über", "miss", "ver", "zer", "ent", "emp", "vor", "auf",
+                "aus", "be", "ge", "un",
+            ],
+            Language::Italian => &[
+                "contro", "sopra", "sotto", "inter", "anti", "dis", "pre", "ri",
+            ],
+            Language::Portuguese => &["contra", "sobre", "entre", "des", "pre", "re"],
+            Language::Spanish => &["contra", "sobre", "entre", "des", "pre", "re"],
+        }
+    }
+
+    /// Common suffixes kept intact as part of the last anchor, e.g. "ing" in "running"
+    pub fn suffixes(&self) -> &'static [&'static str] {
+        match self {
+            Language::Dutch => &["heid", "lijk", "baar", "loos", "isch", "ing", "tje"],
+            Language::English | Language::Auto => &[
+                "ation", "tion", "sion", "ment", "ness", "less", "able", "ible", "ing", "ful",
+                "ous", "est", "ly", "ed",
+            ],
+            Language::French => &[
+                "ement", "ation", "ment", "tion", "able", "isme", "iste", "euse", "eur", "ité",
+            ],
+            Language::German => &[
+                "schaft", "ungen", "heit", "keit", "lich", "isch", "chen", "ung", "bar", "los",
+            ],
+            Language::Italian => &[
+                "mente", "zione", "zioni", "mento", "menti", "abile", "ibile", "ismo", "ista",
+                "ità",
+            ],
+            Language::Portuguese => &[
+                "mente", "ções", "ção", "são", "dade", "ando", "endo", "ador", "ável", "ismo",
+                "ista",
+            ],
+            Language::Spanish => &[
+                "iendo", "mente", "ción", "sión", "idad", "ando", "ador", "able", "ismo", "ista",
+            ],
+        }
+    }
+
+    /// Elided words, written with their apostrophe, that are left untouched in front of
+    /// the word they are joined to, e.g. French "jusqu'" in "jusqu'ici"
+    pub fn elisions(&self) -> &'static [&'static str] {
+        match self {
+            Language::French => &[
+                "c'", "d'", "j'", "l'", "m'", "n'", "s'", "t'", "qu'", "jusqu'", "lorsqu'",
+                "puisqu'", "quoiqu'", "presqu'", "quelqu'",
+            ],
+            Language::Italian => &[
+                "c'", "d'", "l'", "m'", "s'", "t'", "v'", "un'", "all'", "dall'", "dell'", "nell'",
+                "sull'", "coll'", "quell'", "quest'", "bell'", "sant'", "tutt'",
+            ],
+            Language::Portuguese => &["d'"],
+            Language::Dutch
+            | Language::English
+            | Language::German
+            | Language::Spanish
+            | Language::Auto => &[],
+        }
+    }
+
+    /// Clitics, written with the apostrophe or hyphen joining them, that are left
+    /// untouched after the word they are joined to, e.g. French "-elles" in "disent-elles"
+    pub fn clitics(&self) -> &'static [&'static str] {
+        match self {
+            Language::Dutch => &["'k", "'m", "'n", "'s", "'t"],
+            Language::English | Language::Auto => &["'d", "'ll", "'m", "'re", "'s", "'t", "'ve"],
+            Language::French => &[
+                "-ce", "-elle", "-elles", "-en", "-il", "-ils", "-je", "-la", "-le", "-les",
+                "-leur", "-lui", "-moi", "-nous", "-on", "-t", "-toi", "-tu", "-vous", "-y",
+            ],
+            Language::German => &["'s"],
+            Language::Portuguese => &[
+                "-a", "-as", "-la", "-las", "-lhe", "-lhes", "-lo", "-los", "-me", "-na", "-nas",
+                "-no", "-nos", "-o", "-os", "-se", "-te", "-vos",
+            ],
+            Language::Italian | Language::Spanish => &[],
+        }
+    }
+
+    /// Common function words, e.g. "the" or German "und"
+    pub fn stopwords(&self) -> &'static HashSet<&'static str> {
+        match Language::ALL.iter().position(|l| l == self) {
+            Some(index) => &STOPWORDS[index],
+            None => Language::English.stopwords(),
+        }
+    }
+
+    /// Determines if a word is one of the language's stopwords, ignoring case and
+    /// surrounding punctuation
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The word
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not the word is a stopword
+    ///
+    pub fn is_stopword(&self, word: &str) -> bool {
+        let core: &str =
+            word.trim_matches(|c: char| !c.is_alphanumeric() && !APOSTROPHES.contains(&c));
+        self.stopwords()
+            .contains(core.to_lowercase().replace(APOSTROPHES, "'").as_str())
+    }
+
+    /// Whether the language capitalizes every noun, as German does, so that a capital  
+    /// letter says nothing about a word being a name
+    pub fn capitalizes_nouns(&self) -> bool {
+        matches!(self, Language::German)
+    }
+
+    /// Leet substitutions for the language's accented letters as (letter, replacement,
+    /// lowest level) entries, applied by typoglycemia_leet_lang() on top of the
+    /// shared table, e.g. French 'é' => '3' from level 2 up
+    pub fn leet(&self) -> &'static [(char, char, u8)] {
+        match self {
+            Language::Dutch => &[('ë', '3', 2), ('é', '3', 2), ('ï', '1', 2), ('ĳ', 'ÿ', 1)],
+            Language::English | Language::Auto => &[],
+            Language::French => &[
+                ('ç', '¢', 1),
+                ('à', '4', 2),
+                ('é', '3', 2),
+                ('è', '3', 2),
+                ('â', '4', 3),
+                ('ê', '3', 3),
+                ('ô', '0', 3),
+            ],
+            Language::German => &[('ß', 'β', 1), ('ä', '4', 2), ('ö', '0', 2), ('ü', 'µ', 3)],
+            Language::Italian => &[
+                ('à', '4', 2),
+                ('è', '3', 2),
+                ('é', '3', 2),
+                ('ì', '1', 2),
+                ('ò', '0', 2),
+                ('ù', 'µ', 3),
+            ],
+            Language::Portuguese => &[
+                ('ç', '¢', 1),
+                ('ã', '4', 2),
+                ('á', '4', 2),
+                ('é', '3', 2),
+                ('õ', '0', 2),
+                ('ó', '0', 2),
+                ('â', '4', 3),
+                ('ê', '3', 3),
+            ],
+            Language::Spanish => &[
+                ('á', '4', 2),
+                ('é', '3', 2),
+                ('í', '1', 2),
+                ('ó', '0', 2),
+                ('ñ', 'и', 3),
+            ],
+        }
+    }
+
+    /// The language's letter bigram table
+    pub fn bigrams(&self) -> &'static BigramTable {
+        BigramTable::for_language(*self)
+    }
+
+    /// The language's bundled hyphenation patterns
+    pub fn hyphenator(&self) -> &'static Hyphenator {
+        Hyphenator::for_language(*self)
+    }
+}
+
+/// Parses a word list with one entry per line, skipping blank lines and '#' comments
+fn parse_word_list(s: &'static str) -> HashSet<&'static str> {
+    s.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes() {
+        for language in Language::ALL {
+            assert_eq!(Language::from_code(language.code()), Some(language));
+        }
+        assert_eq!(Language::from_code("DE"), Some(Language::German));
+        assert_eq!(Language::from_code("xx"), None);
+        assert_eq!(Language::from_code("auto"), Some(Language::Auto));
+    }
+
+    #[test]
+    fn test_affixes_are_lowercase() {
+        for language in Language::ALL {
+            for affix in language.prefixes().iter().chain(language.suffixes()) {
+                assert_eq!(affix.to_lowercase(), *affix);
+            }
+        }
+    }
+
+    #[test]
+    fn test_profiles() {
+        for language in Language::ALL {
+            assert!(!language.stopwords().is_empty());
+            assert!(language.elisions().iter().all(|e| e.ends_with('\'')));
+            assert!(
+                language
+                    .clitics()
+                    .iter()
+                    .all(|c| c.starts_with('\'') || c.starts_with('-'))
+            );
+        }
+
+        assert!(Language::English.is_stopword("The,"));
+        assert!(Language::English.is_stopword("You\u{2019}re"));
+        assert!(Language::German.is_stopword("und"));
+        assert!(Language::Dutch.is_stopword("een"));
+        assert!(!Language::English.is_stopword("reading"));
+    }
+}
@@ -1,12 +1,62 @@
+mod bigrams;
+mod detection;
+mod entities;
+mod hangul;
+mod html;
+mod hyphenation;
+mod language;
+mod markdown;
+mod markup;
+mod metrics;
+mod numeric;
+mod ramp;
+mod scrambler;
+mod script;
+mod segmentation;
+mod sentences;
+mod stats;
+mod structured;
 mod utilities;
+mod xml;
 
+pub use crate::bigrams::BigramTable;
+pub use crate::detection::{DetectionScope, detect_language};
+pub use crate::html::HtmlOptions;
+use crate::html::scramble_html;
+pub use crate::hyphenation::Hyphenator;
+pub use crate::language::Language;
+use crate::markdown::prose_ranges;
+pub use crate::metrics::{
+    Difficulty, Metric, WordMetrics, damerau_levenshtein, kendall_tau, mean_displacement, measure,
+    measure_text, permutation,
+};
+pub use crate::ramp::{Curve, Ramp, RampUnit};
+pub use crate::scrambler::{
+    CasePolicy, HangulMode, IndicMode, Normalization, NumericPolicy, Scrambler, ShuffleMode,
+    SyllableMode, Target, WordOrder,
+};
+pub use crate::script::Script;
+use crate::script::leet_lookup;
+use crate::segmentation::split_words;
+use crate::sentences::shuffle_word_order;
+pub use crate::stats::{ScrambleStats, SegmentStats};
+pub use crate::structured::{JsonPath, StructuredOptions};
+use crate::structured::{scramble_json, scramble_yaml};
 pub use crate::utilities::utils; // for doctest
-use crate::utilities::utils::scramble_word;
+use crate::utilities::utils::split_whitespace_runs;
+use crate::xml::scramble_xml;
+pub use crate::xml::{XmlOptions, XmlPath};
+use rand::Rng;
+use std::borrow::Cow;
+use std::ops::Range;
 
 /// typoglycemia() takes a string input and will scramble it according to  
 /// typoglycemic rules, i.e. where the first and last character of each word or  
 /// chunk remains in their position, respectively but interior characters  
-/// are randomly shuffled, e.g. "hello" => "hlelo"
+/// are randomly shuffled, e.g. "hello" => "hlelo". Words are rejoined with  
+/// single spaces; typoglycemia_with() keeps the whitespace as-is. Chinese,  
+/// Japanese, Thai, Lao and Khmer text is split into words with the bundled  
+/// dictionaries first, e.g. "研究表明" => "究研明表".
 ///
 /// # Arguments
 ///
@@ -34,13 +84,492 @@ use crate::utilities::utils::scramble_word;
 ///
 /// ```
 pub fn typoglycemia(s: &str) -> String {
-    let tokens: Vec<&str> = s.split_whitespace().collect();
-    let mut vec_of_scrambles: Vec<String> = Vec::new();
+    typoglycemia_with(&single_spaced(s), &Scrambler::default())
+}
+
+/// typoglycemia_lang() behaves the same as typoglycemia() but follows a language's  
+/// profile, e.g. French elisions such as "jusqu'" and clitics such as "-elles" are  
+/// left untouched.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The input string or sentence
+/// - `language` (`Language`) - The language of the input
+///
+/// # Returns
+///
+/// - `String` - A typoglycemified String object
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::{Language, typoglycemia_lang};
+/// let result = typoglycemia_lang("jusqu'ici", Language::French);
+///
+/// assert_eq!(result, "jusqu'ici");
+/// ```
+pub fn typoglycemia_lang(s: &str, language: Language) -> String {
+    typoglycemia_with(&single_spaced(s), &Scrambler::new().with_language(language))
+}
 
-    for tok in tokens.iter() {
-        vec_of_scrambles.push(scramble_word(tok.to_string()));
+/// Rejoins the words of a string with single spaces, as typoglycemia() always has
+fn single_spaced(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// typoglycemia_with() behaves the same as typoglycemia() but lets a Scrambler  
+/// decide which words get scrambled, e.g. only 30% of them, or only the words  
+/// matching a regex or glossary. Unselected words are passed through untouched.  
+/// Unlike typoglycemia(), whitespace is kept as-is instead of being collapsed to  
+/// single spaces, so that line and paragraph breaks survive.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The input string or sentence
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+///
+/// # Returns
+///
+/// - `String` - A typoglycemified String object
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::{Scrambler, Target, typoglycemia_with};
+///
+/// let scrambler = Scrambler::new().with_target(Target::glossary(["world"]));
+/// let result = typoglycemia_with("hello world", &scrambler);
+/// let parts: Vec<&str> = result.split_whitespace().collect();
+///
+/// let v2 = vec!["world", "wolrd", "wlord", "wlrod", "wrlod", "wrold"];
+///
+/// assert_eq!(parts.get(0), Some(&"hello"));
+/// assert!(v2.contains(parts.get(1).unwrap()));
+/// ```
+pub fn typoglycemia_with(s: &str, scrambler: &Scrambler) -> String {
+    typoglycemia_with_stats(s, scrambler).0
+}
+
+/// typoglycemia_with_stats() behaves the same as typoglycemia_with() and also  
+/// reports how many words were scrambled, per sentence or paragraph when the  
+/// Scrambler has a Ramp, along with the rate and intensity the curve gave them.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The input string or passage
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+///
+/// # Returns
+///
+/// - `(String, ScrambleStats)` - A typoglycemified String object and its statistics
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::{Ramp, RampUnit, Scrambler, typoglycemia_with_stats};
+///
+/// let scrambler = Scrambler::new().with_ramp(Ramp::new(RampUnit::Sentence));
+/// let (result, stats) = typoglycemia_with_stats("Almost intact here. Totally scrambled there.", &scrambler);
+///
+/// assert!(result.starts_with("Almost intact here. "));
+/// assert_eq!(stats.segments.len(), 2);
+/// assert_eq!(stats.segments[0].rate, 0.0);
+/// assert_eq!(stats.segments[1].rate, 1.0);
+/// ```
+pub fn typoglycemia_with_stats(s: &str, scrambler: &Scrambler) -> (String, ScrambleStats) {
+    scramble_text(s, scrambler, &mut scrambler.rng())
+}
+
+/// Scrambles text the way typoglycemia_with_stats() does, drawing its randomness from  
+/// the supplied generator so that the text nodes of one document share it
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The input string or passage
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+/// - `rng` (`&mut R`) - The random number generator for this run
+///
+/// # Returns
+///
+/// - `(String, ScrambleStats)` - A typoglycemified String object and its statistics
+///
+fn scramble_text<R: Rng + ?Sized>(
+    s: &str,
+    scrambler: &Scrambler,
+    rng: &mut R,
+) -> (String, ScrambleStats) {
+    let normalized: Cow<str> = scrambler.normalize(s);
+    let reordered: Cow<str> = match scrambler.word_order() {
+        WordOrder::Keep => normalized,
+        WordOrder::Shuffle => Cow::Owned(shuffle_word_order(&normalized, rng)),
+    };
+    let s: &str = &reordered;
+    let mut result: String = String::with_capacity(s.len());
+    let mut stats = ScrambleStats {
+        ramp: scrambler.ramp().cloned(),
+        ..ScrambleStats::default()
+    };
+
+    let segments: Vec<&str> = match scrambler.ramp() {
+        Some(ramp) => ramp.segments(s),
+        None => vec![s],
+    };
+    let count: usize = segments.len();
+
+    // Language::Auto resolves to one scrambler per detected language block
+    let blocks: Vec<(usize, Cow<Scrambler>)> = scrambler
+        .language_blocks(s)
+        .into_iter()
+        .map(|(start, language)| {
+            if language == scrambler.language() {
+                (start, Cow::Borrowed(scrambler))
+            } else {
+                (start, Cow::Owned(scrambler.clone().with_language(language)))
+            }
+        })
+        .collect();
+    let languages: Vec<(usize, Language)> = blocks
+        .iter()
+        .map(|(start, resolved)| (*start, resolved.language()))
+        .collect();
+    let mut spans: Vec<Range<usize>> = scrambler.entity_spans(s, &languages);
+    spans.sort_by_key(|r| r.start);
+    // Merged spans let one cursor walk them alongside the tokens
+    let mut entities: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+    for span in spans {
+        match entities.last_mut() {
+            Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+            _ => entities.push(span),
+        }
+    }
+    let mut cursor: usize = 0;
+    let mut offset: usize = 0;
+
+    for (index, segment) in segments.iter().enumerate() {
+        let mut seg_stats = SegmentStats {
+            index,
+            level: 1.0,
+            rate: 1.0,
+            intensity: 1.0,
+            ..SegmentStats::default()
+        };
+        if let Some(ramp) = scrambler.ramp() {
+            seg_stats.level = ramp.level_at(index, count);
+            seg_stats.rate = ramp.rate_at(index, count);
+            seg_stats.intensity = ramp.intensity_at(index, count);
+        }
+
+        for tok in split_whitespace_runs(segment)
+            .into_iter()
+            .flat_map(split_words)
+        {
+            let position: usize = offset;
+            offset += tok.len();
+            if tok.starts_with(char::is_whitespace) {
+                result.push_str(tok);
+                continue;
+            }
+
+            let active: &Scrambler = blocks
+                .iter()
+                .rev()
+                .find(|(start, _)| *start <= position)
+                .map_or(scrambler, |(_, resolved)| resolved);
+
+            seg_stats.words += 1;
+            while cursor < entities.len() && entities[cursor].end <= position {
+                cursor += 1;
+            }
+            let entity: bool = entities
+                .get(cursor)
+                .is_some_and(|r| r.start < offset && position < r.end);
+            if !entity && active.should_scramble(tok, seg_stats.rate, rng) {
+                let scrambled: String = active.scramble_token(tok, seg_stats.intensity, rng);
+                if scrambled != tok {
+                    seg_stats.scrambled += 1;
+                }
+                result.push_str(&scrambled);
+            } else {
+                result.push_str(tok);
+            }
+        }
+
+        stats.push(seg_stats);
+    }
+
+    (result, stats)
+}
+
+/// typoglycemia_markdown() behaves the same as typoglycemia() on the prose of a  
+/// Markdown document (CommonMark with the GFM extensions): the text of paragraphs,  
+/// headings, lists, tables, block quotes, emphasis and link text. Markup, link  
+/// destinations, code spans, code blocks, HTML, bare URLs and front matter are  
+/// copied byte for byte.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The Markdown source
+///
+/// # Returns
+///
+/// - `String` - The Markdown source with its prose typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::typoglycemia_markdown;
+/// let result = typoglycemia_markdown("# Title\n\nSee [the manual](https://example.com/manual) and `cargo build`.");
+///
+/// assert!(result.starts_with("# T"));
+/// assert!(result.contains("](https://example.com/manual)"));
+/// assert!(result.ends_with(" and `cargo build`."));
+/// ```
+pub fn typoglycemia_markdown(s: &str) -> String {
+    typoglycemia_markdown_with(s, &Scrambler::default())
+}
+
+/// typoglycemia_markdown_with() behaves the same as typoglycemia_markdown() but  
+/// follows the Scrambler's configuration, see typoglycemia_with().
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The Markdown source
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+///
+/// # Returns
+///
+/// - `String` - The Markdown source with its prose typoglycemified
+///
+pub fn typoglycemia_markdown_with(s: &str, scrambler: &Scrambler) -> String {
+    let mut rng = scrambler.rng();
+    let mut result: String = String::with_capacity(s.len());
+    let mut copied: usize = 0;
+
+    for range in prose_ranges(s) {
+        result.push_str(&s[copied..range.start]);
+        result.push_str(&scramble_text(&s[range.clone()], scrambler, &mut rng).0);
+        copied = range.end;
     }
-    vec_of_scrambles.join(" ")
+    result.push_str(&s[copied..]);
+
+    result
+}
+
+/// typoglycemia_html() behaves the same as typoglycemia() but only scrambles the  
+/// visible text of an HTML document. Text inside script, style, code, pre, head,  
+/// template and textarea elements, tags, attributes, comments and words containing  
+/// character references are copied byte for byte. A word split by inline tags is  
+/// scrambled as one word, e.g. "w<b>or</b>d".
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The HTML document
+///
+/// # Returns
+///
+/// - `String` - The HTML document with its text typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::typoglycemia_html;
+/// let result = typoglycemia_html("<p class=\"intro\">Reading <em>scrambled</em> now</p><pre>unchanged</pre>");
+///
+/// assert!(result.starts_with("<p class=\"intro\">R"));
+/// assert!(result.contains("g <em>s"));
+/// assert!(result.ends_with("d</em> now</p><pre>unchanged</pre>"));
+/// ```
+pub fn typoglycemia_html(s: &str) -> String {
+    typoglycemia_html_with(s, &Scrambler::default(), &HtmlOptions::default())
+}
+
+/// typoglycemia_html_with() behaves the same as typoglycemia_html() but follows the  
+/// Scrambler's configuration, see typoglycemia_with(), and the HtmlOptions for what  
+/// to skip and what else to scramble.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The HTML document
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+/// - `options` (`&HtmlOptions`) - The skipped elements, scrambled attributes and entities
+///
+/// # Returns
+///
+/// - `String` - The HTML document with its text typoglycemified
+///
+pub fn typoglycemia_html_with(s: &str, scrambler: &Scrambler, options: &HtmlOptions) -> String {
+    let mut rng = scrambler.rng();
+    scramble_html(s, options, |text| {
+        scramble_text(text, scrambler, &mut rng).0
+    })
+}
+
+/// typoglycemia_xml() behaves the same as typoglycemia() but only scrambles the  
+/// text of an XML document, e.g. DocBook or DITA. Tags, namespace declarations,  
+/// attributes, CDATA sections, comments, processing instructions and the text of  
+/// code and literal elements are copied byte for byte. A word split by inline tags  
+/// such as emphasis is scrambled as one word.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The XML document
+///
+/// # Returns
+///
+/// - `String` - The XML document with its text typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::typoglycemia_xml;
+/// let result = typoglycemia_xml("<para>Install <emphasis>carefully</emphasis> via <command>cargo install</command></para>");
+///
+/// assert!(result.starts_with("<para>I"));
+/// assert!(result.contains("l <emphasis>c"));
+/// assert!(result.ends_with("y</emphasis> via <command>cargo install</command></para>"));
+/// ```
+pub fn typoglycemia_xml(s: &str) -> String {
+    typoglycemia_xml_with(s, &Scrambler::default(), &XmlOptions::default())
+}
+
+/// typoglycemia_xml_with() behaves the same as typoglycemia_xml() but follows the  
+/// Scrambler's configuration, see typoglycemia_with(), and scrambles only the  
+/// elements and attribute values selected by the XmlOptions' paths.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The XML document
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+/// - `options` (`&XmlOptions`) - The selected paths, skipped and inline elements
+///
+/// # Returns
+///
+/// - `String` - The XML document with its selected text typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::{Scrambler, XmlOptions, XmlPath, typoglycemia_xml_with};
+/// let options = XmlOptions::new().with_paths([XmlPath::parse("//title").unwrap()]);
+/// let result = typoglycemia_xml_with("<topic><title>Overview</title><body>Unchanged</body></topic>", &Scrambler::default(), &options);
+///
+/// assert!(result.ends_with("w</title><body>Unchanged</body></topic>"));
+/// ```
+pub fn typoglycemia_xml_with(s: &str, scrambler: &Scrambler, options: &XmlOptions) -> String {
+    let mut rng = scrambler.rng();
+    scramble_xml(s, options, |text| {
+        scramble_text(text, scrambler, &mut rng).0
+    })
+}
+
+/// typoglycemia_json() runs each string value of a JSON document through  
+/// typoglycemia(), leaving keys, numbers, literals, whitespace and escape sequences  
+/// as they were.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The JSON document
+///
+/// # Returns
+///
+/// - `String` - The JSON document with its string values typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::typoglycemia_json;
+/// let result = typoglycemia_json("{\"title\": \"Scrambled\", \"count\": 42}");
+///
+/// assert!(result.starts_with("{\"title\": \"S"));
+/// assert!(result.ends_with("d\", \"count\": 42}"));
+/// ```
+pub fn typoglycemia_json(s: &str) -> String {
+    typoglycemia_json_with(s, &Scrambler::default(), &StructuredOptions::default())
+}
+
+/// typoglycemia_json_with() behaves the same as typoglycemia_json() but follows the  
+/// Scrambler's configuration, see typoglycemia_with(), and only scrambles the string  
+/// values selected by the StructuredOptions' paths and keys.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The JSON document
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+/// - `options` (`&StructuredOptions`) - The selected paths and keys
+///
+/// # Returns
+///
+/// - `String` - The JSON document with its selected string values typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::{JsonPath, Scrambler, StructuredOptions, typoglycemia_json_with};
+/// let options = StructuredOptions::new().with_paths([JsonPath::parse("$.body").unwrap()]);
+/// let result = typoglycemia_json_with("{\"id\": \"unchanged\", \"body\": \"scrambled\"}", &Scrambler::default(), &options);
+///
+/// assert!(result.starts_with("{\"id\": \"unchanged\", \"body\": \"s"));
+/// ```
+pub fn typoglycemia_json_with(
+    s: &str,
+    scrambler: &Scrambler,
+    options: &StructuredOptions,
+) -> String {
+    let mut rng = scrambler.rng();
+    scramble_json(s, options, |text| {
+        scramble_text(text, scrambler, &mut rng).0
+    })
+}
+
+/// typoglycemia_yaml() runs each string value of a YAML document through  
+/// typoglycemia(), leaving keys, numbers, booleans, nulls, comments, anchors, tags,  
+/// indentation and escape sequences as they were. Plain, quoted and block scalars  
+/// are all scrambled. A document that cannot be parsed is returned unchanged.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The YAML document
+///
+/// # Returns
+///
+/// - `String` - The YAML document with its string values typoglycemified
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::typoglycemia_yaml;
+/// let result = typoglycemia_yaml("title: Scrambled  # comment\ncount: 42\n");
+///
+/// assert!(result.starts_with("title: S"));
+/// assert!(result.ends_with("d  # comment\ncount: 42\n"));
+/// ```
+pub fn typoglycemia_yaml(s: &str) -> String {
+    typoglycemia_yaml_with(s, &Scrambler::default(), &StructuredOptions::default())
+}
+
+/// typoglycemia_yaml_with() behaves the same as typoglycemia_yaml() but follows the  
+/// Scrambler's configuration, see typoglycemia_with(), and only scrambles the string  
+/// values selected by the StructuredOptions' paths and keys.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The YAML document
+/// - `scrambler` (`&Scrambler`) - The scrambler configuration
+/// - `options` (`&StructuredOptions`) - The selected paths and keys
+///
+/// # Returns
+///
+/// - `String` - The YAML document with its selected string values typoglycemified
+///
+pub fn typoglycemia_yaml_with(
+    s: &str,
+    scrambler: &Scrambler,
+    options: &StructuredOptions,
+) -> String {
+    let mut rng = scrambler.rng();
+    scramble_yaml(s, options, |text| {
+        scramble_text(text, scrambler, &mut rng).0
+    })
 }
 
 /// typoglycemia_leet() behaves the same as typoglycemia() but will do a  
@@ -66,6 +595,8 @@ pub fn typoglycemia(s: &str) -> String {
 /// 'Y' | 'y' => 'Ч'  
 /// '0' => 'O'  
 ///
+/// Greek and Cyrillic letters go the other way, to Latin look-alikes, at every  
+/// level, see Script::leet(), e.g. Cyrillic 'Я' => 'R' or Greek 'Ρ' => 'P'.
 ///
 /// # Arguments
 ///
@@ -95,62 +626,94 @@ pub fn typoglycemia(s: &str) -> String {
 /// assert!(v1.contains(&result));
 ///
 pub fn typoglycemia_leet(s: &str, level: u8) -> String {
-    let tg: String = typoglycemia(s);
+    typoglycemia_leet_lang(s, level, Language::English)
+}
+
+/// typoglycemia_leet_lang() behaves the same as typoglycemia_leet() but follows a  
+/// language's profile, adding leet substitutions for its accented letters, e.g.  
+/// French 'ç' => '¢'.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The input string or sentence
+/// - `level` (`u8`) - 1-3: Transliteration level with 1 being the most human readable
+/// - `language` (`Language`) - The language of the input
+///
+/// # Returns
+///
+/// - `String` - A typoglycemified String object
+///
+/// # Examples
+///
+/// ```
+/// use typoglycemia::{Language, typoglycemia_leet_lang};
+/// let result = typoglycemia_leet_lang("ça", 1, Language::French);
+///
+/// assert_eq!(result, "¢a");
+/// ```
+pub fn typoglycemia_leet_lang(s: &str, level: u8, language: Language) -> String {
+    let tg: String = typoglycemia_lang(s, language);
+
+    tg.chars()
+        .map(|x| match Script::of(x) {
+            Some(script @ (Script::Greek | Script::Cyrillic)) => {
+                leet_lookup(script.leet(), x, level).unwrap_or(x)
+            }
+            _ => leet_lookup(language.leet(), x, level).unwrap_or_else(|| leet_char(x, level)),
+        })
+        .collect()
+}
 
+/// Leet substitution shared by every language, see typoglycemia_leet()
+fn leet_char(x: char, level: u8) -> char {
     if level == 3 {
-        tg.chars()
-            .map(|x| match x {
-                'A' => 'Д',
-                'B' | 'b' => '8',
-                'c' => '¢',
-                'E' => '€',
-                'e' => '3',
-                'H' => 'н',
-                'I' | 'i' => '1',
-                'M' => 'м',
-                'N' => 'И',
-                'n' => 'и',
-                'O' => '0',
-                'R' => 'Я',
-                'S' | 's' => '$',
-                'v' => '√',
-                'W' => 'Ш',
-                'Y' | 'y' => 'Ч',
-                '0' => 'O',
-                _ => x,
-            })
-            .collect()
+        match x {
+            'A' => 'Д',
+            'B' | 'b' => '8',
+            'c' => '¢',
+            'E' => '€',
+            'e' => '3',
+            'H' => 'н',
+            'I' | 'i' => '1',
+            'M' => 'м',
+            'N' => 'И',
+            'n' => 'и',
+            'O' => '0',
+            'R' => 'Я',
+            'S' | 's' => '$',
+            'v' => '√',
+            'W' => 'Ш',
+            'Y' | 'y' => 'Ч',
+            '0' => 'O',
+            _ => x,
+        }
     } else if level == 2 {
-        tg.chars()
-            .map(|x| match x {
-                'A' => 'Д',
-                'B' | 'b' => '8',
-                'E' | 'e' => '3',
-                'I' | 'i' => '1',
-                'N' => 'И',
-                'n' => 'и',
-                'O' => '0',
-                'R' => 'Я',
-                'S' => '$',
-                'v' => '√',
-                'W' => 'Ш',
-                '0' => 'O',
-                _ => x,
-            })
-            .collect()
+        match x {
+            'A' => 'Д',
+            'B' | 'b' => '8',
+            'E' | 'e' => '3',
+            'I' | 'i' => '1',
+            'N' => 'И',
+            'n' => 'и',
+            'O' => '0',
+            'R' => 'Я',
+            'S' => '$',
+            'v' => '√',
+            'W' => 'Ш',
+            '0' => 'O',
+            _ => x,
+        }
     } else {
-        tg.chars()
-            .map(|x| match x {
-                'A' => 'Д',
-                'B' => '8',
-                'i' => '1',
-                'O' => '0',
-                'R' => 'Я',
-                'v' => '√',
-                'W' => 'Ш',
-                '0' => 'O',
-                _ => x,
-            })
-            .collect()
+        match x {
+            'A' => 'Д',
+            'B' => '8',
+            'i' => '1',
+            'O' => '0',
+            'R' => 'Я',
+            'v' => '√',
+            'W' => 'Ш',
+            '0' => 'O',
+            _ => x,
+        }
     }
 }
@@ -0,0 +1,123 @@
+use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use pulldown_cmark::{Event, LinkType, Options, Parser, Tag, TagEnd};
+
+/// Bare URLs, which GFM turns into links but CommonMark leaves as text
+static BARE_URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:https?://|ftp://|www\.)[^\s<>]*[^\s<>.,;:!?)\]'\x22]").unwrap()
+});
+
+/// Finds the prose of a Markdown document: the text of paragraphs, headings, list
+/// items, table cells, block quotes, emphasis and link text, as byte ranges into the
+/// source. Code spans, code blocks, HTML, link destinations, autolinks, bare URLs,
+/// math and front matter are left out, along with text written with escapes or
+/// entities, e.g. "\*" or "&amp;", so that everything else can be copied as-is.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The Markdown source, CommonMark with the GFM extensions
+///
+/// # Returns
+///
+/// - `Vec<Range<usize>>` - The byte ranges of the prose, in order
+///
+pub(crate) fn prose_ranges(s: &str) -> Vec<Range<usize>> {
+    let options: Options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS
+        | Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS
+        | Options::ENABLE_MATH
+        | Options::ENABLE_GFM;
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut blocks: usize = 0;
+    let mut autolink: bool = false;
+    for (event, range) in Parser::new_ext(s, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::MetadataBlock(_) | Tag::HtmlBlock) => blocks += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::MetadataBlock(_) | TagEnd::HtmlBlock) => {
+                blocks = blocks.saturating_sub(1)
+            }
+            Event::Start(Tag::Link { link_type, .. }) => {
+                autolink = matches!(link_type, LinkType::Autolink | LinkType::Email)
+            }
+            Event::End(TagEnd::Link) => autolink = false,
+            Event::Text(text) if blocks == 0 && !autolink && s[range.clone()] == *text => {
+                // the character after a backslash escape stays where it is
+                let mut range: Range<usize> = range;
+                if s[..range.start].ends_with('\\') {
+                    range.start += text.chars().next().map_or(0, char::len_utf8);
+                }
+                match ranges.last_mut() {
+                    Some(last) if last.end == range.start => last.end = range.end,
+                    _ => ranges.push(range),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // bare URLs stay as they are
+    ranges
+        .into_iter()
+        .flat_map(|range| {
+            let mut pieces: Vec<Range<usize>> = Vec::new();
+            let mut start: usize = range.start;
+            for url in BARE_URL.find_iter(&s[range.clone()]) {
+                pieces.push(start..range.start + url.start());
+                start = range.start + url.end();
+            }
+            pieces.push(start..range.end);
+            pieces
+        })
+        .filter(|range| !range.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prose(s: &str) -> Vec<&str> {
+        prose_ranges(s).into_iter().map(|r| &s[r]).collect()
+    }
+
+    #[test]
+    fn test_prose_ranges() {
+        let s = "---\ntitle: Front matter\n---\n\n# Getting started {#start}\n\n\
+                 Read the *friendly* [manual](https://example.com/manual) or `cargo doc`.\n\n\
+                 ```rust\nlet words = 1;\n```\n\n\
+                 | Column | Other |\n|--------|-------|\n| table  | cells |\n\n\
+                 > Quoted words, see <https://example.org> and www.example.net/docs.\n\n\
+                 Tom \\*escaped\\* &amp; done\n";
+
+        assert_eq!(
+            prose(s),
+            [
+                "Getting started",
+                "Read the ",
+                "friendly",
+                " ",
+                "manual",
+                " or ",
+                ".",
+                "Column",
+                "Other",
+                "table",
+                "cells",
+                "Quoted words, see ",
+                " and ",
+                ".",
+                "Tom ",
+                "escaped",
+                " ",
+                " done",
+            ]
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Character references, e.g. "&amp;", "&#233;" or "&#xE9;"
+static CHARACTER_REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(?:#[0-9]+|#[xX][0-9a-fA-F]+|[A-Za-z][A-Za-z0-9]*);").unwrap());
+
+/// A start or end tag
+#[derive(Debug)]
+pub(crate) struct Tag {
+    /// Element name, as written
+    pub(crate) name: String,
+    /// Whether it is an end tag
+    pub(crate) end: bool,
+    /// Whether it ends with "/>"
+    pub(crate) self_closing: bool,
+    /// Attribute names, as written, and the byte ranges of their values
+    pub(crate) attributes: Vec<(String, Range<usize>)>,
+}
+
+/// Reads a start or end tag, HTML or XML
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `start` (`usize`) - Byte offset of the tag's "<"
+///
+/// # Returns
+///
+/// - `(Tag, usize)` - The tag and the byte offset right after it
+///
+pub(crate) fn read_tag(s: &str, start: usize) -> (Tag, usize) {
+    let bytes: &[u8] = s.as_bytes();
+    let mut i: usize = start + 1;
+    let end: bool = bytes[i] == b'/';
+    if end {
+        i += 1;
+    }
+
+    let name_start: usize = i;
+    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && !b"/>".contains(&bytes[i]) {
+        i += 1;
+    }
+    let name: String = s[name_start..i].to_string();
+
+    let mut attributes: Vec<(String, Range<usize>)> = Vec::new();
+    let mut self_closing: bool = false;
+    while i < bytes.len() && bytes[i] != b'>' {
+        if bytes[i].is_ascii_whitespace() || bytes[i] == b'/' {
+            self_closing = bytes[i] == b'/';
+            i += 1;
+            continue;
+        }
+        self_closing = false;
+
+        let attribute_start: usize = i;
+        while i < bytes.len() && !bytes[i].is_ascii_whitespace() && !b"=/>".contains(&bytes[i]) {
+            i += 1;
+        }
+        let attribute: String = s[attribute_start..i].to_string();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] != b'=' {
+            continue;
+        }
+
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        let value: Range<usize> = match bytes.get(i) {
+            Some(&quote) if quote == b'"' || quote == b'\'' => {
+                let close: usize = s[i + 1..]
+                    .find(quote as char)
+                    .map_or(s.len(), |p| i + 1 + p);
+                let value = i + 1..close;
+                i = (close + 1).min(s.len());
+                value
+            }
+            _ => {
+                let value_start: usize = i;
+                while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                value_start..i
+            }
+        };
+        attributes.push((attribute, value));
+    }
+
+    let tag = Tag {
+        name,
+        end,
+        self_closing,
+        attributes,
+    };
+    (tag, (i + 1).min(s.len()))
+}
+
+/// Decodes the name of a numeric character reference, e.g. "#233" or "#xE9" => 'é'
+///
+/// # Arguments
+///
+/// - `name` (`&str`) - The reference without its "&" and ";"
+///
+/// # Returns
+///
+/// - `Option<char>` - The character, None if the name is not a valid code point
+///
+pub(crate) fn decode_numeric(name: &str) -> Option<char> {
+    if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        return u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+    }
+    name.strip_prefix('#')?
+        .parse::<u32>()
+        .ok()
+        .and_then(char::from_u32)
+}
+
+/// One grapheme of a run: its text and where it is written in the source
+pub(crate) struct Unit {
+    pub(crate) text: String,
+    pub(crate) source: Range<usize>,
+}
+
+/// Escapes a grapheme for HTML or XML text and attribute values
+///
+/// # Arguments
+///
+/// - `grapheme` (`&str`) - The grapheme
+///
+/// # Returns
+///
+/// - `String` - The grapheme with "&", "<" and ">" written as references
+///
+fn escape(grapheme: &str) -> String {
+    grapheme
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Scrambles a run of text nodes as one text and spreads the result back over the
+/// nodes, grapheme by grapheme, so that a word may run through inline tags
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `units` (`&[Unit]`) - The graphemes of the run
+/// - `scramble` (`&mut F`) - Scrambles plain text
+/// - `escape` (`fn(&str) -> String`) - Writes a grapheme missing from the source
+/// - `edits` (`&mut Vec<(Range<usize>, String)>`) - Collects the replacements
+///
+pub(crate) fn scramble_run<F: FnMut(&str) -> String>(
+    s: &str,
+    units: &[Unit],
+    scramble: &mut F,
+    escape: fn(&str) -> String,
+    edits: &mut Vec<(Range<usize>, String)>,
+) {
+    let text: String = units.iter().map(|u| u.text.as_str()).collect();
+    if !text.chars().any(char::is_alphabetic) {
+        return;
+    }
+
+    let scrambled: String = scramble(&text);
+    let graphemes: Vec<&str> = scrambled.graphemes(true).collect();
+    if graphemes.len() != units.len() {
+        return;
+    }
+
+    // each grapheme is written the way it was written in the source
+    let mut sources: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (index, unit) in units.iter().enumerate() {
+        sources
+            .entry(unit.text.as_str())
+            .or_default()
+            .push_back(index);
+    }
+    for (unit, grapheme) in units.iter().zip(graphemes) {
+        let written: String = match sources.get_mut(grapheme).and_then(VecDeque::pop_front) {
+            Some(index) => s[units[index].source.clone()].to_string(),
+            None => escape(grapheme),
+        };
+        if written != s[unit.source.clone()] {
+            edits.push((unit.source.clone(), written));
+        }
+    }
+}
+
+/// Collects the graphemes of a text node into runs. Character references become
+/// single graphemes when decoded, and split the run otherwise.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `node` (`Range<usize>`) - The text node
+/// - `decode` (`fn(&str) -> Option<char>`) - Decodes a character reference, None splits the run
+/// - `run` (`&mut Vec<Unit>`) - The current run
+/// - `runs` (`&mut Vec<Vec<Unit>>`) - The finished runs
+///
+pub(crate) fn collect_units(
+    s: &str,
+    node: Range<usize>,
+    decode: fn(&str) -> Option<char>,
+    run: &mut Vec<Unit>,
+    runs: &mut Vec<Vec<Unit>>,
+) {
+    let plain = |range: Range<usize>, run: &mut Vec<Unit>| {
+        for (offset, grapheme) in s[range.clone()].grapheme_indices(true) {
+            run.push(Unit {
+                text: grapheme.to_string(),
+                source: range.start + offset..range.start + offset + grapheme.len(),
+            });
+        }
+    };
+
+    let mut start: usize = node.start;
+    for reference in CHARACTER_REFERENCE.find_iter(&s[node.clone()]) {
+        let source: Range<usize> = node.start + reference.start()..node.start + reference.end();
+        plain(start..source.start, run);
+        match decode(reference.as_str()) {
+            Some(c) => run.push(Unit {
+                text: c.to_string(),
+                source: source.clone(),
+            }),
+            None => runs.push(std::mem::take(run)),
+        }
+        start = source.end;
+    }
+    plain(start..node.end, run);
+}
+
+/// Scrambles each run and writes the result back into the document, copying every
+/// byte outside the runs as it was
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `runs` (`&[Vec<Unit>]`) - The runs, in any order
+/// - `scramble` (`F`) - Scrambles plain text
+///
+/// # Returns
+///
+/// - `String` - The document with its runs scrambled
+///
+pub(crate) fn scramble_runs<F: FnMut(&str) -> String>(
+    s: &str,
+    runs: &[Vec<Unit>],
+    mut scramble: F,
+) -> String {
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    for units in runs.iter().filter(|units| !units.is_empty()) {
+        scramble_run(s, units, &mut scramble, escape, &mut edits);
+    }
+
+    apply_edits(s, edits)
+}
+
+/// Writes replacements into a document, copying every other byte as it was
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `edits` (`Vec<(Range<usize>, String)>`) - The replacements, not overlapping
+///
+/// # Returns
+///
+/// - `String` - The document with the replacements
+///
+pub(crate) fn apply_edits(s: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start);
+
+    let mut result: String = String::with_capacity(s.len());
+    let mut copied: usize = 0;
+    for (range, written) in edits {
+        result.push_str(&s[copied..range.start]);
+        result.push_str(&written);
+        copied = range.end;
+    }
+    result.push_str(&s[copied..]);
+
+    result
+}
@@ -0,0 +1,367 @@
+//! Readability metrics comparing a word or text with its scrambled version.
+//! Every measure works on extended grapheme clusters, e.g.
+//!
+//! ```
+//! use typoglycemia::{BigramTable, damerau_levenshtein, measure};
+//!
+//! assert_eq!(damerau_levenshtein("hello", "hlelo"), 1);
+//!
+//! let m = measure("world", "wrlod", BigramTable::english());
+//! assert_eq!(m.distance, 2);
+//! assert_eq!(m.displacement, 0.8);
+//! ```
+
+use std::collections::HashMap;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::bigrams::BigramTable;
+
+/// The measures computed by measure() and measure_text()
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WordMetrics {
+    /// Damerau-Levenshtein distance, in graphemes
+    pub distance: usize,
+    /// Kendall tau of the permutation, 1.0 for intact and -1.0 for reversed
+    pub kendall_tau: f64,
+    /// Mean distance each grapheme moved
+    pub displacement: f64,
+    /// Fraction of the scrambled letter pairs that are illegal
+    pub illegal_bigrams: f64,
+}
+
+impl WordMetrics {
+    /// Reads one measure as a number
+    pub fn get(&self, metric: Metric) -> f64 {
+        match metric {
+            Metric::Distance => self.distance as f64,
+            Metric::KendallTau => self.kendall_tau,
+            Metric::Displacement => self.displacement,
+            Metric::IllegalBigrams => self.illegal_bigrams,
+        }
+    }
+}
+
+/// Selects one of the WordMetrics measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Damerau-Levenshtein distance
+    Distance,
+    /// Kendall tau of the permutation (lower is harder)
+    KendallTau,
+    /// Mean letter displacement
+    Displacement,
+    /// Fraction of illegal bigrams
+    IllegalBigrams,
+}
+
+/// Difficulty asks the scrambler to resample each word's interior until the chosen
+/// metric lands in the `min..=max` band. Words that cannot reach the band within
+/// `attempts` tries keep the closest sample, e.g. short words.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    metric: Metric,
+    min: f64,
+    max: f64,
+    attempts: usize,
+}
+
+impl Difficulty {
+    /// Targets `metric` in the `min..=max` band with 50 attempts per word
+    pub fn new(metric: Metric, min: f64, max: f64) -> Self {
+        Difficulty {
+            metric,
+            min: min.min(max),
+            max: max.max(min),
+            attempts: 50,
+        }
+    }
+
+    /// Sets the number of samples drawn per word, at least 1
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// The metric to target
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    /// Lower bound of the band, inclusive
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Upper bound of the band, inclusive
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Number of samples to draw per word before settling
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// How far a score falls outside the band, 0.0 inside of it
+    pub fn miss(&self, score: f64) -> f64 {
+        if score < self.min {
+            self.min - score
+        } else if score > self.max {
+            score - self.max
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Damerau-Levenshtein distance (insertions, deletions, substitutions and
+/// transpositions of adjacent graphemes) between two strings
+///
+/// # Arguments
+///
+/// - `a` (`&str`) - The first string
+/// - `b` (`&str`) - The second string
+///
+/// # Returns
+///
+/// - `usize` - The edit distance
+///
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<&str> = a.graphemes(true).collect();
+    let b: Vec<&str> = b.graphemes(true).collect();
+    let max_dist: usize = a.len() + b.len();
+
+    // rows and columns are offset by one to hold the max_dist sentinel
+    let width: usize = b.len() + 2;
+    let mut d: Vec<usize> = vec![0; (a.len() + 2) * width];
+    d[0] = max_dist;
+    for i in 0..=a.len() {
+        d[(i + 1) * width] = max_dist;
+        d[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        d[j + 1] = max_dist;
+        d[width + j + 1] = j;
+    }
+
+    let mut last_row: HashMap<&str, usize> = HashMap::new();
+    for i in 1..=a.len() {
+        let mut last_match_col: usize = 0;
+        for j in 1..=b.len() {
+            let k: usize = last_row.get(b[j - 1]).copied().unwrap_or(0);
+            let l: usize = last_match_col;
+            let cost: usize = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+
+            d[(i + 1) * width + j + 1] = (d[i * width + j] + cost)
+                .min(d[(i + 1) * width + j] + 1)
+                .min(d[i * width + j + 1] + 1)
+                .min(d[k * width + l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    d[(a.len() + 1) * width + b.len() + 1]
+}
+
+/// Recovers the permutation turning `original` into `scrambled`: entry `i` is the
+/// original position of the grapheme now at position `i`. Repeated graphemes are
+/// matched in order.
+///
+/// # Arguments
+///
+/// - `original` (`&str`) - The word before scrambling
+/// - `scrambled` (`&str`) - The word after scrambling
+///
+/// # Returns
+///
+/// - `Option<Vec<usize>>` - The permutation, None if the graphemes differ
+///
+pub fn permutation(original: &str, scrambled: &str) -> Option<Vec<usize>> {
+    let original: Vec<&str> = original.graphemes(true).collect();
+    let scrambled: Vec<&str> = scrambled.graphemes(true).collect();
+    if original.len() != scrambled.len() {
+        return None;
+    }
+
+    let mut used: Vec<bool> = vec![false; original.len()];
+    let mut perm: Vec<usize> = Vec::with_capacity(scrambled.len());
+    for g in scrambled {
+        let index: usize = (0..original.len()).find(|&i| !used[i] && original[i] == g)?;
+        used[index] = true;
+        perm.push(index);
+    }
+
+    Some(perm)
+}
+
+/// Kendall tau rank correlation between a permutation and the identity
+///
+/// # Arguments
+///
+/// - `perm` (`&[usize]`) - The permutation, see permutation()
+///
+/// # Returns
+///
+/// - `f64` - 1.0 when intact, -1.0 when reversed, 1.0 for fewer than two items
+///
+pub fn kendall_tau(perm: &[usize]) -> f64 {
+    let n: usize = perm.len();
+    if n < 2 {
+        return 1.0;
+    }
+
+    let mut discordant: usize = 0;
+    for i in 0..n {
+        for j in i + 1..n {
+            if perm[i] > perm[j] {
+                discordant += 1;
+            }
+        }
+    }
+
+    let pairs: f64 = (n * (n - 1) / 2) as f64;
+    1.0 - 2.0 * discordant as f64 / pairs
+}
+
+/// Mean number of positions each grapheme moved
+///
+/// # Arguments
+///
+/// - `perm` (`&[usize]`) - The permutation, see permutation()
+///
+/// # Returns
+///
+/// - `f64` - The mean displacement, 0.0 for an empty permutation
+///
+pub fn mean_displacement(perm: &[usize]) -> f64 {
+    if perm.is_empty() {
+        return 0.0;
+    }
+
+    let total: usize = perm.iter().enumerate().map(|(i, &p)| i.abs_diff(p)).sum();
+    total as f64 / perm.len() as f64
+}
+
+/// Computes every metric for a single word
+///
+/// # Arguments
+///
+/// - `original` (`&str`) - The word before scrambling
+/// - `scrambled` (`&str`) - The word after scrambling
+/// - `table` (`&BigramTable`) - The language's bigram table
+///
+/// # Returns
+///
+/// - `WordMetrics` - The metrics. Permutation based metrics read as intact when
+///   the two words are not permutations of each other.
+///
+pub fn measure(original: &str, scrambled: &str, table: &BigramTable) -> WordMetrics {
+    let perm: Vec<usize> = permutation(original, scrambled).unwrap_or_default();
+
+    WordMetrics {
+        distance: damerau_levenshtein(original, scrambled),
+        kendall_tau: kendall_tau(&perm),
+        displacement: mean_displacement(&perm),
+        illegal_bigrams: table.illegal_fraction(scrambled),
+    }
+}
+
+/// Averages the metrics over the whitespace-delimited words of a text. The
+/// distance is summed rather than averaged.
+///
+/// # Arguments
+///
+/// - `original` (`&str`) - The text before scrambling
+/// - `scrambled` (`&str`) - The text after scrambling
+/// - `table` (`&BigramTable`) - The language's bigram table
+///
+/// # Returns
+///
+/// - `WordMetrics` - The aggregated metrics
+///
+pub fn measure_text(original: &str, scrambled: &str, table: &BigramTable) -> WordMetrics {
+    let words: Vec<WordMetrics> = original
+        .split_whitespace()
+        .zip(scrambled.split_whitespace())
+        .map(|(o, s)| measure(o, s, table))
+        .collect();
+
+    if words.is_empty() {
+        return WordMetrics {
+            kendall_tau: 1.0,
+            ..WordMetrics::default()
+        };
+    }
+
+    let n: f64 = words.len() as f64;
+    WordMetrics {
+        distance: words.iter().map(|m| m.distance).sum(),
+        kendall_tau: words.iter().map(|m| m.kendall_tau).sum::<f64>() / n,
+        displacement: words.iter().map(|m| m.displacement).sum::<f64>() / n,
+        illegal_bigrams: words.iter().map(|m| m.illegal_bigrams).sum::<f64>() / n,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        assert_eq!(damerau_levenshtein("", ""), 0);
+        assert_eq!(damerau_levenshtein("abc", ""), 3);
+        assert_eq!(damerau_levenshtein("kitten", "sitting"), 3);
+        assert_eq!(damerau_levenshtein("ca", "abc"), 2);
+        assert_eq!(damerau_levenshtein("hello", "hlelo"), 1);
+        assert_eq!(damerau_levenshtein("❤️ab", "❤️ba"), 1);
+    }
+
+    #[test]
+    fn test_permutation() {
+        assert_eq!(permutation("abcd", "acbd"), Some(vec![0, 2, 1, 3]));
+        assert_eq!(
+            permutation("letter", "lettre"),
+            Some(vec![0, 1, 2, 3, 5, 4])
+        );
+        assert_eq!(permutation("abc", "abd"), None);
+        assert_eq!(permutation("abc", "ab"), None);
+    }
+
+    #[test]
+    fn test_kendall_tau_and_displacement() {
+        assert_eq!(kendall_tau(&[0, 1, 2, 3]), 1.0);
+        assert_eq!(kendall_tau(&[3, 2, 1, 0]), -1.0);
+        assert_eq!(kendall_tau(&[0]), 1.0);
+
+        assert_eq!(mean_displacement(&[0, 1, 2, 3]), 0.0);
+        assert_eq!(mean_displacement(&[3, 2, 1, 0]), 2.0);
+        assert_eq!(mean_displacement(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_measure_text() {
+        let table = BigramTable::english();
+        let intact = measure_text("hello world", "hello world", table);
+        assert_eq!(intact.distance, 0);
+        assert_eq!(intact.kendall_tau, 1.0);
+
+        let scrambled = measure_text("hello world", "hlelo wlrod", table);
+        assert_eq!(scrambled.distance, 3);
+        assert!(scrambled.kendall_tau < 1.0);
+    }
+
+    #[test]
+    fn test_difficulty_band() {
+        let d = Difficulty::new(Metric::Displacement, 1.5, 0.5).with_attempts(0);
+        assert_eq!((d.min(), d.max(), d.attempts()), (0.5, 1.5, 1));
+        assert_eq!(d.miss(1.0), 0.0);
+        assert_eq!(d.miss(0.25), 0.25);
+        assert_eq!(d.miss(2.0), 0.5);
+    }
+}
@@ -0,0 +1,114 @@
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Opening punctuation trimmed off a token before it is matched, e.g. "(3rd"
+const LEADING_PUNCTUATION: &[char] = &['(', '[', '{', '"', '\'', '«', '“', '‘', '¿', '¡'];
+
+/// Closing punctuation trimmed off a token before it is matched, e.g. "$100,"
+const TRAILING_PUNCTUATION: &[char] = &[
+    ')', ']', '}', '"', '\'', '»', '”', '’', '.', ',', ';', ':', '!', '?', '…',
+];
+
+/// Shapes of tokens that read as numbers rather than words, one per kind
+static NUMERIC_TOKENS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    [
+        // numbers, ranges, scores, dates, times and phone numbers made of digit groups,
+        // e.g. 1,000.50, 36-26-36, 12/22/1986, 15:32, +1-555-123-4567
+        r"^[+\-−±]?\p{Nd}+(?:[.,:/\-–_' ]\p{Nd}+)*%?$",
+        // phone numbers with an area code in parentheses, e.g. (555)123-4567
+        r"^\+?\(?\d+\)\d+(?:[\-. ]\d+)*$",
+        // ordinals, e.g. 3rd, 21st, 2e, 1er, 3ème, 1º
+        r"^\d+(?:st|nd|rd|th|d|e|er|re|ère|ème|eme|º|ª)$",
+        // currency amounts, e.g. $100, €5.99, 100€, £2.5bn
+        r"^[+\-]?\p{Sc}\d[\d.,]*(?:k|m|bn|mn)?$",
+        r"^\d[\d.,]*\p{Sc}$",
+        // versions, e.g. v1.2.3, 2.0.1-beta.2, v3
+        r"^v?\d+(?:\.\d+)+(?:[\-+][0-9a-z.\-]+)?$",
+        r"^v\d+$",
+        // hex values, colours and commit hashes, e.g. 0xFF, #c0ffee, 4d3a10d
+        r"^0x[0-9a-f]+$",
+        r"^#(?:[0-9a-f]{3}|[0-9a-f]{6}|[0-9a-f]{8})$",
+        r"^[0-9a-f]{7,40}$",
+        // ISO 8601 timestamps and clock times, e.g. 2024-01-15T10:30:00Z, 5pm, 10:30a.m
+        r"^\d{4}-\d{2}-\d{2}t\d{2}:\d{2}(?::\d{2}(?:\.\d+)?)?(?:z|[+\-]\d{2}:?\d{2})?$",
+        r"^\d{1,2}(?::\d{2}){0,2}(?:am|pm|a\.m|p\.m|h)$",
+        // dimensions and quantities, e.g. 4x4, 1920x1080, 10km, 2.5GHz
+        r"^\d+(?:x\d+)+$",
+        r"^\d[\d.,]*(?:mm|cm|km|m|kg|mg|g|lbs?|oz|ml|l|ms|s|min|hz|khz|mhz|ghz|kb|mb|gb|tb|px|pt|k|x|°[cf]?)$",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(&format!("(?i){pattern}")).unwrap())
+    .collect()
+});
+
+/// Determines if a token reads as a number: a plain number, ordinal, currency amount,
+/// version, hex value, phone number, date or time, wherever its digits are, e.g.
+/// "3rd", "$100", "v1.2.3" and "0xFF" but not "3D-printed" or "Win10"
+///
+/// # Arguments
+///
+/// - `token` (`&str`) - The token, surrounding punctuation included
+///
+/// # Returns
+///
+/// - `bool` - Whether or not the token should be kept as-is
+///
+pub(crate) fn is_numeric_token(token: &str) -> bool {
+    let core: &str = token
+        .trim_start_matches(LEADING_PUNCTUATION)
+        .trim_end_matches(TRAILING_PUNCTUATION);
+
+    core.chars().any(|c| c.is_numeric()) && NUMERIC_TOKENS.iter().any(|re| re.is_match(core))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_numeric_token() {
+        let numeric = [
+            "12345",
+            "1,000.50",
+            "(3rd)",
+            "21st,",
+            "1er",
+            "$100",
+            "€5.99.",
+            "100€",
+            "v1.2.3",
+            "2.0.1-beta.2",
+            "0xFF",
+            "#c0ffee",
+            "4d3a10d",
+            "+1-555-123-4567",
+            "(555)123-4567",
+            "12/22/1986",
+            "2024-01-15T10:30:00Z",
+            "15:32",
+            "5pm",
+            "4x4",
+            "2.5GHz",
+            "١٩٨٦/١٢/٢٢",
+        ];
+        for token in numeric {
+            assert!(is_numeric_token(token), "{token}");
+        }
+
+        let words = [
+            "hello",
+            "3D-printed",
+            "Win10",
+            "abc123xyz",
+            "#facade",
+            "#hashtag",
+            "v",
+            "1-for-all",
+            "2nd-hand",
+            "decade",
+        ];
+        for token in words {
+            assert!(!is_numeric_token(token), "{token}");
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use regex::Regex;
+use std::sync::LazyLock;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Blank lines, i.e. two or more line breaks with only whitespace between them
+static PARAGRAPH_BREAK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\r?\n(?:[^\S\n]*\r?\n)+[^\S\n]*").unwrap());
+
+/// How the difficulty grows from the first segment to the last one
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Curve {
+    /// Grows at a constant pace (default)
+    #[default]
+    Linear,
+    /// Starts slow and speeds up (quadratic)
+    EaseIn,
+    /// Starts fast and slows down (quadratic)
+    EaseOut,
+    /// Caller supplied curve mapping 0.0 - 1.0 progress to a 0.0 - 1.0 level
+    Custom(fn(f64) -> f64),
+}
+
+impl Curve {
+    /// Maps progress through the passage to a difficulty level
+    ///
+    /// # Arguments
+    ///
+    /// - `t` (`f64`) - Progress, 0.0 at the first segment and 1.0 at the last
+    ///
+    /// # Returns
+    ///
+    /// - `f64` - The difficulty level, clamped to 0.0 - 1.0
+    ///
+    pub fn apply(&self, t: f64) -> f64 {
+        let level: f64 = match self {
+            Curve::Linear => t,
+            Curve::EaseIn => t * t,
+            Curve::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Curve::Custom(f) => f(t),
+        };
+
+        if level.is_nan() {
+            0.0
+        } else {
+            level.clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// The unit the ramp steps through
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RampUnit {
+    /// Unicode sentences (UAX #29) (default)
+    #[default]
+    Sentence,
+    /// Blocks of text separated by blank lines
+    Paragraph,
+}
+
+/// Ramp makes a passage start almost intact and get progressively more scrambled.
+/// Both the scramble rate (share of words scrambled) and the intensity (share of
+/// each word's interior that is shuffled) move from their start to their end value
+/// following the curve, e.g.
+///
+/// ```
+/// use typoglycemia::{Curve, Ramp, RampUnit};
+///
+/// let ramp = Ramp::new(RampUnit::Paragraph)
+///     .with_curve(Curve::EaseIn)
+///     .with_rate(0.1, 1.0)
+///     .with_intensity(0.5, 1.0);
+///
+/// assert_eq!(ramp.rate_at(0, 3), 0.1);
+/// assert_eq!(ramp.intensity_at(2, 3), 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Ramp {
+    unit: RampUnit,
+    curve: Curve,
+    rate: (f64, f64),
+    intensity: (f64, f64),
+}
+
+impl Ramp {
+    /// Returns a linear ramp from intact (0.0) to fully scrambled (1.0)
+    ///
+    /// # Arguments
+    ///
+    /// - `unit` (`RampUnit`) - Step by sentence or by paragraph
+    ///
+    pub fn new(unit: RampUnit) -> Self {
+        Ramp {
+            unit,
+            curve: Curve::Linear,
+            rate: (0.0, 1.0),
+            intensity: (0.0, 1.0),
+        }
+    }
+
+    /// Sets the curve the rate and intensity follow from the first to the last segment
+    ///
+    /// # Arguments
+    ///
+    /// - `curve` (`Curve`) - Linear, ease-in, ease-out or a custom function
+    ///
+    pub fn with_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Sets the scramble rate at the first and last segment
+    ///
+    /// # Arguments
+    ///
+    /// - `start` (`f64`) - Share of words scrambled in the first segment, clamped to 0.0 - 1.0
+    /// - `end` (`f64`) - Share of words scrambled in the last segment, clamped to 0.0 - 1.0
+    ///
+    pub fn with_rate(mut self, start: f64, end: f64) -> Self {
+        self.rate = (start.clamp(0.0, 1.0), end.clamp(0.0, 1.0));
+        self
+    }
+
+    /// Sets the intensity at the first and last segment
+    ///
+    /// # Arguments
+    ///
+    /// - `start` (`f64`) - Share of each word's interior shuffled in the first segment, clamped to 0.0 - 1.0
+    /// - `end` (`f64`) - Share of each word's interior shuffled in the last segment, clamped to 0.0 - 1.0
+    ///
+    pub fn with_intensity(mut self, start: f64, end: f64) -> Self {
+        self.intensity = (start.clamp(0.0, 1.0), end.clamp(0.0, 1.0));
+        self
+    }
+
+    /// The unit the ramp steps through
+    pub fn unit(&self) -> RampUnit {
+        self.unit
+    }
+
+    /// The curve
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Difficulty level, per the curve, of segment `index` out of `count`
+    ///
+    /// # Arguments
+    ///
+    /// - `index` (`usize`) - The zero-based segment index
+    /// - `count` (`usize`) - The number of segments in the passage
+    ///
+    /// # Returns
+    ///
+    /// - `f64` - The level, 0.0 - 1.0
+    ///
+    pub fn level_at(&self, index: usize, count: usize) -> f64 {
+        let t: f64 = if count <= 1 {
+            0.0
+        } else {
+            index.min(count - 1) as f64 / (count - 1) as f64
+        };
+
+        self.curve.apply(t)
+    }
+
+    /// Scramble rate of segment `index` out of `count`
+    pub fn rate_at(&self, index: usize, count: usize) -> f64 {
+        lerp(self.rate, self.level_at(index, count))
+    }
+
+    /// Intensity of segment `index` out of `count`
+    pub fn intensity_at(&self, index: usize, count: usize) -> f64 {
+        lerp(self.intensity, self.level_at(index, count))
+    }
+
+    /// Splits text into the ramp's segments. Joining the segments gives back the
+    /// original text, whitespace included. Whitespace-only sentences are folded
+    /// into the sentence before them.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The passage
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<&str>` - The sentences or paragraphs, in order
+    ///
+    pub fn segments<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        match self.unit {
+            RampUnit::Sentence => {
+                let starts = s
+                    .split_sentence_bound_indices()
+                    .filter(|(_, sentence)| !sentence.trim().is_empty())
+                    .map(|(offset, _)| offset);
+                split_at_starts(s, starts)
+            }
+            RampUnit::Paragraph => {
+                let starts = PARAGRAPH_BREAK.find_iter(s).map(|m| m.end());
+                split_at_starts(s, starts)
+            }
+        }
+    }
+}
+
+/// Cuts text at the given segment start offsets, keeping every byte
+fn split_at_starts(s: &str, starts: impl Iterator<Item = usize>) -> Vec<&str> {
+    let mut segments: Vec<&str> = Vec::new();
+    let mut start: usize = 0;
+
+    for offset in starts {
+        if offset > start && offset < s.len() {
+            segments.push(&s[start..offset]);
+            start = offset;
+        }
+    }
+
+    if start < s.len() {
+        segments.push(&s[start..]);
+    }
+
+    segments
+}
+
+/// Linear interpolation between a (start, end) pair
+fn lerp((start, end): (f64, f64), level: f64) -> f64 {
+    start + (end - start) * level
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curves() {
+        assert_eq!(Curve::Linear.apply(0.5), 0.5);
+        assert_eq!(Curve::EaseIn.apply(0.5), 0.25);
+        assert_eq!(Curve::EaseOut.apply(0.5), 0.75);
+        assert_eq!(Curve::Custom(|t| t * 4.0).apply(0.5), 1.0);
+        assert_eq!(Curve::Custom(|_| f64::NAN).apply(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_levels() {
+        let ramp = Ramp::new(RampUnit::Sentence).with_rate(0.2, 1.0);
+        assert_eq!(ramp.rate_at(0, 5), 0.2);
+        assert_eq!(ramp.rate_at(4, 5), 1.0);
+        assert_eq!(ramp.intensity_at(2, 5), 0.5);
+
+        // a single segment sits at the start of the ramp
+        assert_eq!(ramp.rate_at(0, 1), 0.2);
+    }
+
+    #[test]
+    fn test_segments() {
+        let text = "First one. Second one!\n\n  Third one?\n \nFourth";
+
+        let sentences = Ramp::new(RampUnit::Sentence).segments(text);
+        assert_eq!(
+            sentences,
+            vec![
+                "First one. ",
+                "Second one!\n\n",
+                "  Third one?\n \n",
+                "Fourth"
+            ]
+        );
+
+        let paragraphs = Ramp::new(RampUnit::Paragraph).segments(text);
+        assert_eq!(
+            paragraphs,
+            vec!["First one. Second one!\n\n  ", "Third one?\n \n", "Fourth"]
+        );
+    }
+}
@@ -0,0 +1,1027 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+use rand::{Rng, SeedableRng, rng, rngs::StdRng};
+use regex::Regex;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::bigrams::BigramTable;
+use crate::detection::{DetectionScope, detect_language};
+use crate::entities::find_entities;
+use crate::hyphenation::Hyphenator;
+use crate::language::Language;
+use crate::metrics::{Difficulty, measure};
+use crate::ramp::{Ramp, RampUnit};
+use crate::utilities::utils::{is_acronym, restore_case, scramble_word_with};
+
+/// Chooses which words are candidates for scrambling.
+/// Selectors look at the word with its leading and trailing punctuation trimmed,
+/// e.g. "(hello," is matched as "hello".
+#[derive(Debug, Clone, Default)]
+pub enum Target {
+    /// Every word is a candidate (default)
+    #[default]
+    All,
+    /// Only words matching the regular expression
+    Pattern(Regex),
+    /// Only words found in the glossary (lowercase entries, matched case-insensitively)
+    Glossary(HashSet<String>),
+}
+
+impl Target {
+    /// Builds a regex selector
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern` (`&str`) - A regular expression, e.g. `"^(un|re)"`
+    ///
+    /// # Returns
+    ///
+    /// - `Result<Target, regex::Error>` - The selector, or the regex compilation error
+    ///
+    pub fn pattern(pattern: &str) -> Result<Target, regex::Error> {
+        Ok(Target::Pattern(Regex::new(pattern)?))
+    }
+
+    /// Builds a glossary selector, lowercasing each entry
+    ///
+    /// # Arguments
+    ///
+    /// - `words` (`I`) - The glossary entries
+    ///
+    /// # Returns
+    ///
+    /// - `Target` - The glossary selector
+    ///
+    pub fn glossary<I, S>(words: I) -> Target
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Target::Glossary(
+            words
+                .into_iter()
+                .map(|w| w.as_ref().to_lowercase())
+                .collect(),
+        )
+    }
+
+    /// Determines if a word is selected by this target
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The word, possibly with surrounding punctuation
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not the word is a scramble candidate
+    ///
+    pub fn matches(&self, word: &str) -> bool {
+        let core: &str = word.trim_matches(|c: char| !c.is_alphanumeric());
+
+        match self {
+            Target::All => true,
+            Target::Pattern(re) => re.is_match(core),
+            Target::Glossary(set) => set.contains(&core.to_lowercase()),
+        }
+    }
+}
+
+/// How the interior of a word is reordered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShuffleMode {
+    /// Every ordering is equally likely (default)
+    #[default]
+    Uniform,
+    /// Prefer orderings whose letter bigrams occur in the language
+    Readable,
+    /// Prefer orderings with as many illegal letter bigrams as possible
+    Hard,
+}
+
+/// Unicode normalization applied to the input before scrambling. Combining marks,
+/// variation selectors and ZWJ sequences always travel with their base grapheme,
+/// whatever the form.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Normalization {
+    /// Keep the input's form (default)
+    #[default]
+    Preserve,
+    /// Compose to NFC, e.g. "e" + U+0301 => "é"
+    Nfc,
+    /// Decompose to NFD, e.g. "é" => "e" + U+0301
+    Nfd,
+}
+
+/// How syllable boundaries constrain the shuffle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SyllableMode {
+    /// Syllables are ignored (default)
+    #[default]
+    Off,
+    /// Letters only move within their own syllable, e.g. "hy-phen-ation" => "hy-pehn-atoin"
+    Within,
+    /// Whole syllables are shuffled, the first and last syllables stay put,
+    /// e.g. "un-be-liev-able" => "un-liev-be-able"
+    Whole,
+}
+
+/// How Korean words made of Hangul syllable blocks are scrambled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HangulMode {
+    /// Whole syllable blocks are shuffled, the first and last blocks stay put (default)
+    #[default]
+    Blocks,
+    /// Blocks are decomposed into jamo, initial consonants, vowels and final consonants  
+    /// are each shuffled among their own position, and valid blocks are recomposed. The  
+    /// first initial consonant and the final jamo of the word stay put, e.g. "한국어" => "훈각어" or "학운거"
+    Jamo,
+}
+
+/// How words in Indic scripts are cut into shuffle units
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IndicMode {
+    /// Extended grapheme clusters, which keep vowel signs with their consonant but may  
+    /// still split a conjunct, e.g. Tamil "க்ஷ" (default)
+    #[default]
+    Graphemes,
+    /// Aksharas, the orthographic syllables: every consonant joined by a virama, plus  
+    /// the vowel signs and marks that follow, e.g. Tamil "க்ஷே" or Hindi "स्ते". The  
+    /// first and last aksharas stay put.
+    Aksharas,
+}
+
+/// Whether the order of the words in each sentence is shuffled too
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WordOrder {
+    /// Words stay in order (default)
+    #[default]
+    Keep,
+    /// The words between the first and last word of each sentence are shuffled,  
+    /// punctuation staying in place, e.g. "The quick brown fox jumps." => "The fox  
+    /// quick brown jumps." Each word is then scrambled as usual.
+    Shuffle,
+}
+
+/// Where capital letters end up when the interior of a word is shuffled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CasePolicy {
+    /// Capitals move with their letter, e.g. "McDonald" => "MDcnoald" (default)
+    #[default]
+    Letters,
+    /// Capitalization stays with the position, e.g. "McDonald" => "MnDoclad"
+    Positions,
+    /// Capital letters stay in place like anchors, e.g. "McDonald" => "McDlanod"
+    Pinned,
+}
+
+/// Which tokens containing digits are kept as-is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NumericPolicy {
+    /// Any token starting with a digit is kept, e.g. "12/22/1986" but also "3rdparty",  
+    /// while "$100" or "v1.2.3" are scrambled like words (default)
+    #[default]
+    Leading,
+    /// Numbers, ordinals, currency amounts, versions, hex values, phone numbers, dates  
+    /// and times are kept wherever their digits are, e.g. "$100", "v1.2.3", "0xFF" and  
+    /// "+1-555-123-4567", and other tokens are scrambled, e.g. "2nd-hand" => "2nd-hnad"
+    Recognize,
+    /// Same as Recognize, but the letters of other mixed alphanumerics are shuffled  
+    /// among themselves with the digits fixed in place, e.g. "Covid19-related" => "Cviod19-rletaed"
+    ScrambleLetters,
+}
+
+/// Scrambler holds the configuration consumed by typoglycemia_with(), e.g.
+///
+/// ```
+/// use typoglycemia::{Scrambler, Target, typoglycemia_with};
+///
+/// let scrambler = Scrambler::new()
+///     .with_probability(0.3)
+///     .with_target(Target::pattern("ing$").unwrap())
+///     .with_seed(42);
+///
+/// let result = typoglycemia_with("reading and writing", &scrambler);
+/// assert!(result.contains(" and "));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scrambler {
+    probability: f64,
+    target: Target,
+    ramp: Option<Ramp>,
+    difficulty: Option<Difficulty>,
+    shuffle_mode: ShuffleMode,
+    language: Language,
+    detection_scope: DetectionScope,
+    preserve_clusters: bool,
+    clusters: Option<Vec<String>>,
+    preserve_affixes: bool,
+    affixes: Option<(Vec<String>, Vec<String>)>,
+    preserve_stopwords: bool,
+    preserve_acronyms: bool,
+    preserve_entities: bool,
+    gazetteer: Vec<String>,
+    split_subwords: bool,
+    word_order: WordOrder,
+    case_policy: CasePolicy,
+    syllable_mode: SyllableMode,
+    hangul_mode: HangulMode,
+    indic_mode: IndicMode,
+    numeric_policy: NumericPolicy,
+    normalization: Normalization,
+    hyphenator: Option<Arc<Hyphenator>>,
+    seed: Option<u64>,
+}
+
+impl Default for Scrambler {
+    fn default() -> Self {
+        Scrambler {
+            probability: 1.0,
+            target: Target::All,
+            ramp: None,
+            difficulty: None,
+            shuffle_mode: ShuffleMode::Uniform,
+            language: Language::English,
+            detection_scope: DetectionScope::Document,
+            preserve_clusters: false,
+            clusters: None,
+            preserve_affixes: false,
+            affixes: None,
+            preserve_stopwords: false,
+            preserve_acronyms: false,
+            preserve_entities: false,
+            gazetteer: Vec::new(),
+            split_subwords: false,
+            word_order: WordOrder::Keep,
+            case_policy: CasePolicy::Letters,
+            syllable_mode: SyllableMode::Off,
+            hangul_mode: HangulMode::Blocks,
+            indic_mode: IndicMode::Graphemes,
+            numeric_policy: NumericPolicy::Leading,
+            normalization: Normalization::Preserve,
+            hyphenator: None,
+            seed: None,
+        }
+    }
+}
+
+impl Scrambler {
+    /// Returns a scrambler that behaves like typoglycemia()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the per-word scramble probability, clamped to 0.0 - 1.0
+    ///
+    /// # Arguments
+    ///
+    /// - `probability` (`f64`) - The chance that a selected word is scrambled, e.g. 0.3 for 30%
+    ///
+    pub fn with_probability(mut self, probability: f64) -> Self {
+        self.probability = if probability.is_nan() {
+            0.0
+        } else {
+            probability.clamp(0.0, 1.0)
+        };
+        self
+    }
+
+    /// Restricts scrambling to the words selected by `target`
+    ///
+    /// # Arguments
+    ///
+    /// - `target` (`Target`) - The word selector
+    ///
+    pub fn with_target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Ramps the difficulty up across the passage's sentences or paragraphs.
+    /// The ramp's rate is multiplied with the per-word probability.
+    ///
+    /// # Arguments
+    ///
+    /// - `ramp` (`Ramp`) - The difficulty ramp
+    ///
+    pub fn with_ramp(mut self, ramp: Ramp) -> Self {
+        self.ramp = Some(ramp);
+        self
+    }
+
+    /// Resamples each scrambled word until its score lands in the difficulty band
+    ///
+    /// # Arguments
+    ///
+    /// - `difficulty` (`Difficulty`) - The metric and band to target
+    ///
+    pub fn with_difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = Some(difficulty);
+        self
+    }
+
+    /// Sets how each word's interior is reordered
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` (`ShuffleMode`) - Uniform, readable or hard
+    ///
+    pub fn with_shuffle_mode(mut self, mode: ShuffleMode) -> Self {
+        self.shuffle_mode = mode;
+        self
+    }
+
+    /// Sets the language whose profile drives elisions, clitics, clusters, affixes,  
+    /// stopwords, syllables, the readable and hard modes and the metrics
+    ///
+    /// # Arguments
+    ///
+    /// - `language` (`Language`) - The language of the input text
+    ///
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Sets how much text Language::Auto detects a language for
+    ///
+    /// # Arguments
+    ///
+    /// - `scope` (`DetectionScope`) - The whole document or each paragraph
+    ///
+    pub fn with_detection_scope(mut self, scope: DetectionScope) -> Self {
+        self.detection_scope = scope;
+        self
+    }
+
+    /// Keeps the language's digraphs and trigraphs, e.g. "th", "qu" or German "sch",  
+    /// together as indivisible units while shuffling
+    ///
+    /// # Arguments
+    ///
+    /// - `preserve` (`bool`) - Whether or not to keep clusters together
+    ///
+    pub fn with_cluster_preservation(mut self, preserve: bool) -> Self {
+        self.preserve_clusters = preserve;
+        self
+    }
+
+    /// Keeps a custom list of clusters together instead of the language's list, e.g.  
+    /// Dutch "ij". Turns cluster preservation on.
+    ///
+    /// # Arguments
+    ///
+    /// - `clusters` (`I`) - The clusters, matched case-insensitively
+    ///
+    pub fn with_clusters<I, S>(mut self, clusters: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.preserve_clusters = true;
+        self.clusters = Some(
+            clusters
+                .into_iter()
+                .map(|c| c.as_ref().to_lowercase())
+                .filter(|c| !c.is_empty())
+                .collect(),
+        );
+        self
+    }
+
+    /// Keeps the language's common prefixes and suffixes, e.g. "un", "-ing" or  
+    /// Spanish "-ción", intact as extended anchors so that only the stem is shuffled
+    ///
+    /// # Arguments
+    ///
+    /// - `preserve` (`bool`) - Whether or not to keep affixes intact
+    ///
+    pub fn with_affix_preservation(mut self, preserve: bool) -> Self {
+        self.preserve_affixes = preserve;
+        self
+    }
+
+    /// Keeps custom prefixes and suffixes intact instead of the language's lists.  
+    /// Turns affix preservation on.
+    ///
+    /// # Arguments
+    ///
+    /// - `prefixes` (`P`) - The prefixes, matched case-insensitively
+    /// - `suffixes` (`X`) - The suffixes, matched case-insensitively
+    ///
+    pub fn with_affixes<P, X, S1, S2>(mut self, prefixes: P, suffixes: X) -> Self
+    where
+        P: IntoIterator<Item = S1>,
+        X: IntoIterator<Item = S2>,
+        S1: AsRef<str>,
+        S2: AsRef<str>,
+    {
+        self.preserve_affixes = true;
+        self.affixes = Some((lowercase_affixes(prefixes), lowercase_affixes(suffixes)));
+        self
+    }
+
+    /// Leaves the language's stopwords, e.g. "which" or German "dieser", unscrambled
+    ///
+    /// # Arguments
+    ///
+    /// - `preserve` (`bool`) - Whether or not to skip stopwords
+    ///
+    pub fn with_stopword_preservation(mut self, preserve: bool) -> Self {
+        self.preserve_stopwords = preserve;
+        self
+    }
+
+    /// Leaves all-caps acronyms, e.g. "NASA", "HTML5" or "FAQs", unscrambled
+    ///
+    /// # Arguments
+    ///
+    /// - `preserve` (`bool`) - Whether or not to skip acronyms
+    ///
+    pub fn with_acronym_preservation(mut self, preserve: bool) -> Self {
+        self.preserve_acronyms = preserve;
+        self
+    }
+
+    /// Leaves names readable: capitalized words that do not start a sentence, unless  
+    /// the language capitalizes its nouns, and well-known multi-word names such as  
+    /// "New York" or "San Francisco", wherever they are
+    ///
+    /// # Arguments
+    ///
+    /// - `preserve` (`bool`) - Whether or not to skip named entities
+    ///
+    pub fn with_entity_preservation(mut self, preserve: bool) -> Self {
+        self.preserve_entities = preserve;
+        self
+    }
+
+    /// Leaves the names of a gazetteer unscrambled, on top of the named entities found  
+    /// by with_entity_preservation(), e.g. ["Zorblax", "Acme Widgets"]. Entries are  
+    /// matched case-sensitively, word by word.
+    ///
+    /// # Arguments
+    ///
+    /// - `entries` (`I`) - The names, single or multi-word
+    ///
+    pub fn with_gazetteer<I, S>(mut self, entries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.preserve_entities = true;
+        self.gazetteer = entries
+            .into_iter()
+            .map(|e| e.as_ref().trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect();
+        self
+    }
+
+    /// Splits camelCase, PascalCase and snake_case words into their components, each  
+    /// scrambled with its own anchors, e.g. "getUserName" => "getUesrNmae" and  
+    /// "#ThrowbackThursday" => "#TcrbowhakTrusdahy"
+    ///
+    /// # Arguments
+    ///
+    /// - `split` (`bool`) - Whether or not to scramble subwords separately
+    ///
+    pub fn with_subword_splitting(mut self, split: bool) -> Self {
+        self.split_subwords = split;
+        self
+    }
+
+    /// Shuffles the words inside each sentence as well, keeping the first and last word  
+    /// and the punctuation in place. Combine with `.with_probability(0.0)` to reorder  
+    /// the words without scrambling them.
+    ///
+    /// # Arguments
+    ///
+    /// - `order` (`WordOrder`) - Keep or shuffle
+    ///
+    pub fn with_word_order(mut self, order: WordOrder) -> Self {
+        self.word_order = order;
+        self
+    }
+
+    /// Chooses whether capitals move with their letters, stay with their positions or  
+    /// stay in place, e.g. for "iPhone", "JavaScript" or "LaTeX"
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` (`CasePolicy`) - Letters, positions or pinned
+    ///
+    pub fn with_case_policy(mut self, policy: CasePolicy) -> Self {
+        self.case_policy = policy;
+        self
+    }
+
+    /// Confines the shuffle to syllables, or shuffles whole syllables. Syllables come  
+    /// from the language's bundled hyphenation patterns unless with_hyphenator() is used.
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` (`SyllableMode`) - Off, within or whole
+    ///
+    pub fn with_syllable_mode(mut self, mode: SyllableMode) -> Self {
+        self.syllable_mode = mode;
+        self
+    }
+
+    /// Finds syllables with custom patterns instead of the language's bundled ones, e.g.  
+    /// patterns loaded with Hyphenator::from_file()
+    ///
+    /// # Arguments
+    ///
+    /// - `hyphenator` (`Hyphenator`) - The hyphenator
+    ///
+    pub fn with_hyphenator(mut self, hyphenator: Hyphenator) -> Self {
+        self.hyphenator = Some(Arc::new(hyphenator));
+        self
+    }
+
+    /// Shuffles Korean words block by block, or jamo by jamo within their positions
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` (`HangulMode`) - Blocks or jamo
+    ///
+    pub fn with_hangul_mode(mut self, mode: HangulMode) -> Self {
+        self.hangul_mode = mode;
+        self
+    }
+
+    /// Shuffles words in Indic scripts by grapheme, or by akshara
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` (`IndicMode`) - Graphemes or aksharas
+    ///
+    pub fn with_indic_mode(mut self, mode: IndicMode) -> Self {
+        self.indic_mode = mode;
+        self
+    }
+
+    /// Chooses which tokens containing digits are kept as-is
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` (`NumericPolicy`) - Leading digits, recognized numbers, or recognized  
+    ///   numbers with the letters of other alphanumerics shuffled around fixed digits
+    ///
+    pub fn with_numeric_policy(mut self, policy: NumericPolicy) -> Self {
+        self.numeric_policy = policy;
+        self
+    }
+
+    /// Normalizes the input to NFC or NFD before scrambling, or keeps its form
+    ///
+    /// # Arguments
+    ///
+    /// - `normalization` (`Normalization`) - Preserve, NFC or NFD
+    ///
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Seeds the random number generator so that output is reproducible
+    ///
+    /// # Arguments
+    ///
+    /// - `seed` (`u64`) - The seed
+    ///
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The per-word scramble probability
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
+
+    /// The word selector
+    pub fn target(&self) -> &Target {
+        &self.target
+    }
+
+    /// The difficulty ramp, if any
+    pub fn ramp(&self) -> Option<&Ramp> {
+        self.ramp.as_ref()
+    }
+
+    /// The targeted difficulty, if any
+    pub fn difficulty(&self) -> Option<&Difficulty> {
+        self.difficulty.as_ref()
+    }
+
+    /// How each word's interior is reordered
+    pub fn shuffle_mode(&self) -> ShuffleMode {
+        self.shuffle_mode
+    }
+
+    /// The language of the input text
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// How much text Language::Auto detects a language for
+    pub fn detection_scope(&self) -> DetectionScope {
+        self.detection_scope
+    }
+
+    /// How syllable boundaries constrain the shuffle
+    pub fn syllable_mode(&self) -> SyllableMode {
+        self.syllable_mode
+    }
+
+    /// How Korean words are scrambled
+    pub fn hangul_mode(&self) -> HangulMode {
+        self.hangul_mode
+    }
+
+    /// How words in Indic scripts are cut into shuffle units
+    pub fn indic_mode(&self) -> IndicMode {
+        self.indic_mode
+    }
+
+    /// Whether camelCase, PascalCase and snake_case components are scrambled separately
+    pub fn subword_splitting(&self) -> bool {
+        self.split_subwords
+    }
+
+    /// Whether the order of the words in each sentence is shuffled too
+    pub fn word_order(&self) -> WordOrder {
+        self.word_order
+    }
+
+    /// Where capital letters end up when the interior of a word is shuffled
+    pub fn case_policy(&self) -> CasePolicy {
+        self.case_policy
+    }
+
+    /// Which tokens containing digits are kept as-is
+    pub fn numeric_policy(&self) -> NumericPolicy {
+        self.numeric_policy
+    }
+
+    /// The Unicode normalization applied to the input
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Applies the normalization to the input text
+    pub(crate) fn normalize<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self.normalization {
+            Normalization::Preserve => Cow::Borrowed(s),
+            Normalization::Nfc => Cow::Owned(s.nfc().collect()),
+            Normalization::Nfd => Cow::Owned(s.nfd().collect()),
+        }
+    }
+
+    /// The hyphenator used to find syllables
+    pub(crate) fn hyphenator(&self) -> &Hyphenator {
+        match &self.hyphenator {
+            Some(custom) => custom,
+            None => self.language.hyphenator(),
+        }
+    }
+
+    /// The clusters kept together while shuffling, lowercase and longest first.  
+    /// Empty when cluster preservation is off.
+    pub(crate) fn active_clusters(&self) -> Vec<String> {
+        if !self.preserve_clusters {
+            return Vec::new();
+        }
+
+        let mut clusters: Vec<String> = match &self.clusters {
+            Some(custom) => custom.clone(),
+            None => self
+                .language
+                .clusters()
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+        };
+        clusters.sort_by_key(|c| std::cmp::Reverse(c.graphemes(true).count()));
+        clusters
+    }
+
+    /// The prefixes and suffixes kept intact, lowercase and longest first.  
+    /// Empty when affix preservation is off.
+    pub(crate) fn active_affixes(&self) -> (Vec<String>, Vec<String>) {
+        if !self.preserve_affixes {
+            return (Vec::new(), Vec::new());
+        }
+
+        let (mut prefixes, mut suffixes): (Vec<String>, Vec<String>) = match &self.affixes {
+            Some(custom) => custom.clone(),
+            None => (
+                self.language
+                    .prefixes()
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect(),
+                self.language
+                    .suffixes()
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect(),
+            ),
+        };
+        prefixes.sort_by_key(|a| std::cmp::Reverse(a.graphemes(true).count()));
+        suffixes.sort_by_key(|a| std::cmp::Reverse(a.graphemes(true).count()));
+        (prefixes, suffixes)
+    }
+
+    /// Splits text into blocks that share one bundled language, detecting it when the  
+    /// language is Auto. Text without letters takes the previous block's language,  
+    /// or English.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The input text
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<(usize, Language)>` - Each block's starting byte offset and language
+    ///
+    pub(crate) fn language_blocks(&self, s: &str) -> Vec<(usize, Language)> {
+        if self.language != Language::Auto {
+            return vec![(0, self.language)];
+        }
+
+        let paragraphs: Vec<&str> = match self.detection_scope {
+            DetectionScope::Document => vec![s],
+            DetectionScope::Paragraph => Ramp::new(RampUnit::Paragraph).segments(s),
+        };
+
+        let mut blocks: Vec<(usize, Language)> = Vec::new();
+        let mut start: usize = 0;
+        let mut previous: Language = Language::English;
+        for paragraph in paragraphs {
+            previous = detect_language(paragraph).unwrap_or(previous);
+            blocks.push((start, previous));
+            start += paragraph.len();
+        }
+
+        if blocks.is_empty() {
+            blocks.push((0, previous));
+        }
+        blocks
+    }
+
+    /// Finds the byte ranges of the named entities to leave unscrambled, none unless  
+    /// entity preservation is on
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The input text
+    /// - `languages` (`&[(usize, Language)]`) - Each language block's starting byte offset and language
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<Range<usize>>` - The byte ranges of the entities
+    ///
+    pub(crate) fn entity_spans(
+        &self,
+        s: &str,
+        languages: &[(usize, Language)],
+    ) -> Vec<Range<usize>> {
+        match self.preserve_entities {
+            true => find_entities(s, &self.gazetteer, languages),
+            false => Vec::new(),
+        }
+    }
+
+    /// Builds the random number generator for one run, seeded if requested
+    pub(crate) fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_rng(&mut rng()),
+        }
+    }
+
+    /// Decides whether a word should be handed to scramble_word()
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The whitespace-delimited token
+    /// - `rate` (`f64`) - The current segment's scramble rate, 1.0 without a ramp
+    /// - `rng` (`&mut R`) - The random number generator for this run
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not to scramble the word
+    ///
+    pub(crate) fn should_scramble<R: Rng + ?Sized>(
+        &self,
+        word: &str,
+        rate: f64,
+        rng: &mut R,
+    ) -> bool {
+        if !self.target.matches(word) {
+            return false;
+        }
+        if self.preserve_stopwords && self.language.is_stopword(word) {
+            return false;
+        }
+        if self.preserve_acronyms && is_acronym(word) {
+            return false;
+        }
+
+        let p: f64 = (self.probability * rate).clamp(0.0, 1.0);
+        p >= 1.0 || rng.random_bool(p)
+    }
+
+    /// Scrambles a selected word, resampling it until it meets the difficulty  
+    /// band when one is set
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The whitespace-delimited token
+    /// - `intensity` (`f64`) - The current segment's intensity, 1.0 without a ramp
+    /// - `rng` (`&mut R`) - The random number generator for this run
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The scrambled word
+    ///
+    pub(crate) fn scramble_token<R: Rng + ?Sized>(
+        &self,
+        word: &str,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        let Some(difficulty) = self.difficulty else {
+            let sample: String = scramble_word_with(word.to_string(), self, intensity, rng);
+            return self.recase(word, sample);
+        };
+
+        let table: &BigramTable = self.language.bigrams();
+        let mut best: Option<(f64, String)> = None;
+        for _ in 0..difficulty.attempts() {
+            let sample: String = scramble_word_with(word.to_string(), self, intensity, rng);
+            let miss: f64 = difficulty.miss(measure(word, &sample, table).get(difficulty.metric()));
+            if miss == 0.0 {
+                return self.recase(word, sample);
+            }
+            if best.as_ref().is_none_or(|(m, _)| miss < *m) {
+                best = Some((miss, sample));
+            }
+        }
+
+        best.map(|(_, sample)| self.recase(word, sample))
+            .unwrap_or_else(|| word.to_string())
+    }
+
+    /// Moves capitalization back to its original positions under CasePolicy::Positions
+    fn recase(&self, word: &str, sample: String) -> String {
+        match self.case_policy {
+            CasePolicy::Positions => restore_case(word, &sample),
+            _ => sample,
+        }
+    }
+}
+
+/// Lowercases custom affixes for case-insensitive matching, dropping empty ones
+///
+/// # Arguments
+///
+/// - `affixes` (`I`) - The prefixes or suffixes
+///
+/// # Returns
+///
+/// - `Vec<String>` - The lowercased affixes
+///
+fn lowercase_affixes<I, S>(affixes: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    affixes
+        .into_iter()
+        .map(|a| a.as_ref().to_lowercase())
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{Metric, kendall_tau, permutation};
+
+    #[test]
+    fn test_target_matches() {
+        let glossary = Target::glossary(["Reading", "fluency"]);
+        assert!(glossary.matches("reading"));
+        assert!(glossary.matches("(Fluency),"));
+        assert!(!glossary.matches("writing"));
+
+        let pattern = Target::pattern("ing$").unwrap();
+        assert!(pattern.matches("writing."));
+        assert!(!pattern.matches("written"));
+
+        assert!(Target::All.matches("anything"));
+        assert!(Target::pattern("(").is_err());
+    }
+
+    #[test]
+    fn test_probability_bounds() {
+        let mut r = rng();
+
+        let never = Scrambler::new().with_probability(-1.0);
+        assert_eq!(never.probability(), 0.0);
+        assert!(!(0..100).any(|_| never.should_scramble("hello", 1.0, &mut r)));
+
+        let always = Scrambler::new().with_probability(7.0);
+        assert_eq!(always.probability(), 1.0);
+        assert!((0..100).all(|_| always.should_scramble("hello", 1.0, &mut r)));
+    }
+
+    #[test]
+    fn test_probability_fraction() {
+        let scrambler = Scrambler::new().with_probability(0.3).with_seed(7);
+        let mut r = scrambler.rng();
+        let hits = (0..10_000)
+            .filter(|_| scrambler.should_scramble("hello", 1.0, &mut r))
+            .count();
+
+        assert!((2_500..3_500).contains(&hits));
+    }
+
+    #[test]
+    fn test_difficulty_band() {
+        let hard = Scrambler::new().with_difficulty(Difficulty::new(Metric::KendallTau, -1.0, 0.3));
+        let mut r = rng();
+
+        for _ in 0..20 {
+            let result = hard.scramble_token("remarkable", 1.0, &mut r);
+            let perm = permutation("remarkable", &result).unwrap();
+            assert!(kendall_tau(&perm) <= 0.3);
+        }
+
+        // "the" can't be scrambled, the closest sample is returned
+        assert_eq!(hard.scramble_token("the", 1.0, &mut r), "the");
+    }
+
+    #[test]
+    fn test_active_clusters() {
+        assert!(Scrambler::new().active_clusters().is_empty());
+
+        let german = Scrambler::new()
+            .with_language(Language::German)
+            .with_cluster_preservation(true);
+        assert_eq!(german.active_clusters()[0], "tsch");
+
+        let dutch = Scrambler::new().with_clusters(["IJ", "", "sch"]);
+        assert_eq!(dutch.active_clusters(), vec!["sch", "ij"]);
+    }
+
+    #[test]
+    fn test_language_blocks() {
+        let text = "The weather is lovely today.\n\nIl fait très beau aujourd'hui.\n\n42";
+
+        let fixed = Scrambler::new().with_language(Language::German);
+        assert_eq!(fixed.language_blocks(text), vec![(0, Language::German)]);
+
+        let document = Scrambler::new().with_language(Language::Auto);
+        assert_eq!(document.language_blocks(text).len(), 1);
+
+        let paragraphs = document.with_detection_scope(DetectionScope::Paragraph);
+        assert_eq!(
+            paragraphs.language_blocks(text),
+            vec![
+                (0, Language::English),
+                (30, Language::French),
+                (63, Language::French)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_active_affixes() {
+        assert_eq!(Scrambler::new().active_affixes(), (vec![], vec![]));
+
+        let english = Scrambler::new().with_affix_preservation(true);
+        let (prefixes, suffixes) = english.active_affixes();
+        assert_eq!(prefixes[0].len(), 5);
+        assert!(suffixes.contains(&"ing".to_string()));
+
+        let custom = Scrambler::new().with_affixes(["UN"], vec!["ly", "ness"]);
+        assert_eq!(
+            custom.active_affixes(),
+            (
+                vec!["un".to_string()],
+                vec!["ness".to_string(), "ly".to_string()]
+            )
+        );
+
+        let mixed = Scrambler::new().with_affixes(["pre"], ["ism", "ness"]);
+        assert_eq!(mixed.active_affixes().0, vec!["pre".to_string()]);
+    }
+}
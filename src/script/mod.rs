@@ -0,0 +1,223 @@
+/// Viramas of the Indic scripts from Devanagari to Malayalam, which kill the inherent
+/// vowel of a consonant and join it to the next one
+const VIRAMAS: &[char] = &[
+    '\u{094D}', '\u{09CD}', '\u{0A4D}', '\u{0ACD}', '\u{0B4D}', '\u{0BCD}', '\u{0C4D}', '\u{0CCD}',
+    '\u{0D4D}',
+];
+
+/// Script identifies the alphabets typoglycemia anchors words in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// Latin, including the Latin-1 accented letters
+    Latin,
+    /// Greek, including polytonic Greek
+    Greek,
+    /// Cyrillic
+    Cyrillic,
+}
+
+impl Script {
+    /// Finds the script a letter belongs to
+    ///
+    /// # Arguments
+    ///
+    /// - `c` (`char`) - The character
+    ///
+    /// # Returns
+    ///
+    /// - `Option<Script>` - The script, None for digits, punctuation and other scripts
+    ///
+    pub fn of(c: char) -> Option<Script> {
+        match c {
+            'A'..='Z' | 'a'..='z' | 'À'..='ÿ' if c.is_alphabetic() => Some(Script::Latin),
+            '\u{0386}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' if c.is_alphabetic() => {
+                Some(Script::Greek)
+            }
+            '\u{0400}'..='\u{04FF}' if c.is_alphabetic() => Some(Script::Cyrillic),
+            _ => None,
+        }
+    }
+
+    /// Reverse leet substitutions turning letters into Latin look-alikes and digits,
+    /// as (letter, replacement, lowest level) entries. Where a letter has several
+    /// entries the one with the highest level not above the chosen level wins, e.g.
+    /// Cyrillic 'О' => 'O' at level 1 but '0' at level 3. Latin letters use the shared
+    /// table of typoglycemia_leet() instead.
+    pub fn leet(&self) -> &'static [(char, char, u8)] {
+        match self {
+            Script::Latin => &[],
+            Script::Greek => &[
+                ('Α', 'A', 1),
+                ('Β', 'B', 1),
+                ('Ε', 'E', 1),
+                ('Ζ', 'Z', 1),
+                ('Η', 'H', 1),
+                ('Ι', 'I', 1),
+                ('Κ', 'K', 1),
+                ('Μ', 'M', 1),
+                ('Ν', 'N', 1),
+                ('Ο', 'O', 1),
+                ('Ρ', 'P', 1),
+                ('Τ', 'T', 1),
+                ('Υ', 'Y', 1),
+                ('Χ', 'X', 1),
+                ('ι', 'i', 1),
+                ('κ', 'k', 1),
+                ('ν', 'v', 1),
+                ('ο', 'o', 1),
+                ('ρ', 'p', 1),
+                ('υ', 'u', 1),
+                ('χ', 'x', 1),
+                ('α', 'a', 2),
+                ('β', 'ß', 2),
+                ('γ', 'y', 2),
+                ('ε', 'e', 2),
+                ('η', 'n', 2),
+                ('μ', 'u', 2),
+                ('π', 'n', 2),
+                ('σ', 'o', 2),
+                ('ς', 's', 2),
+                ('τ', 't', 2),
+                ('ω', 'w', 2),
+                ('Α', '4', 3),
+                ('α', '4', 3),
+                ('Β', '8', 3),
+                ('β', '8', 3),
+                ('Ε', '3', 3),
+                ('ε', '3', 3),
+                ('Ζ', '2', 3),
+                ('Ι', '1', 3),
+                ('ι', '1', 3),
+                ('Ο', '0', 3),
+                ('ο', '0', 3),
+                ('Θ', '0', 3),
+                ('θ', '0', 3),
+            ],
+            Script::Cyrillic => &[
+                ('А', 'A', 1),
+                ('В', 'B', 1),
+                ('Е', 'E', 1),
+                ('К', 'K', 1),
+                ('М', 'M', 1),
+                ('Н', 'H', 1),
+                ('О', 'O', 1),
+                ('Р', 'P', 1),
+                ('С', 'C', 1),
+                ('Т', 'T', 1),
+                ('Х', 'X', 1),
+                ('а', 'a', 1),
+                ('е', 'e', 1),
+                ('о', 'o', 1),
+                ('р', 'p', 1),
+                ('с', 'c', 1),
+                ('у', 'y', 1),
+                ('х', 'x', 1),
+                ('Я', 'R', 2),
+                ('я', 'R', 2),
+                ('И', 'N', 2),
+                ('и', 'u', 2),
+                ('Ш', 'W', 2),
+                ('ш', 'w', 2),
+                ('Ч', 'Y', 2),
+                ('Д', 'D', 2),
+                ('к', 'k', 2),
+                ('м', 'm', 2),
+                ('п', 'n', 2),
+                ('т', 't', 2),
+                ('ь', 'b', 2),
+                ('О', '0', 3),
+                ('о', '0', 3),
+                ('З', '3', 3),
+                ('з', '3', 3),
+                ('Э', '3', 3),
+                ('Ч', '4', 3),
+                ('ч', '4', 3),
+                ('Б', '6', 3),
+                ('б', '6', 3),
+                ('В', '8', 3),
+                ('в', '8', 3),
+            ],
+        }
+    }
+}
+
+/// Looks a character up in a leet table, see Script::leet()
+///
+/// # Arguments
+///
+/// - `table` (`&[(char, char, u8)]`) - (letter, replacement, lowest level) entries
+/// - `c` (`char`) - The character
+/// - `level` (`u8`) - The chosen leet level
+///
+/// # Returns
+///
+/// - `Option<char>` - The replacement, None if the table has none at this level
+///
+pub(crate) fn leet_lookup(table: &[(char, char, u8)], c: char, level: u8) -> Option<char> {
+    table
+        .iter()
+        .filter(|(letter, _, min_level)| *letter == c && level >= *min_level)
+        .max_by_key(|(_, _, min_level)| *min_level)
+        .map(|(_, replacement, _)| *replacement)
+}
+
+/// Determines if a grapheme ends with a virama, possibly followed by a zero width
+/// joiner or non-joiner, and so belongs to the same akshara as the next grapheme, e.g.
+/// Tamil "க்" in "க்ஷ"
+///
+/// # Arguments
+///
+/// - `grapheme` (`&str`) - The grapheme
+///
+/// # Returns
+///
+/// - `bool` - Whether or not the grapheme ends with a virama
+///
+pub(crate) fn ends_with_virama(grapheme: &str) -> bool {
+    grapheme
+        .trim_end_matches(['\u{200C}', '\u{200D}'])
+        .ends_with(VIRAMAS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_of() {
+        assert_eq!(Script::of('a'), Some(Script::Latin));
+        assert_eq!(Script::of('é'), Some(Script::Latin));
+        assert_eq!(Script::of('λ'), Some(Script::Greek));
+        assert_eq!(Script::of('ῶ'), Some(Script::Greek));
+        assert_eq!(Script::of('ж'), Some(Script::Cyrillic));
+        assert_eq!(Script::of('×'), None);
+        assert_eq!(Script::of('7'), None);
+    }
+
+    #[test]
+    fn test_ends_with_virama() {
+        assert!(ends_with_virama("க\u{BCD}"));
+        assert!(ends_with_virama("स\u{94D}\u{200D}"));
+        assert!(!ends_with_virama("स\u{94D}ते"));
+        assert!(!ends_with_virama("k"));
+    }
+
+    #[test]
+    fn test_leet_lookup() {
+        let cyrillic = Script::Cyrillic.leet();
+        assert_eq!(leet_lookup(cyrillic, 'О', 1), Some('O'));
+        assert_eq!(leet_lookup(cyrillic, 'О', 2), Some('O'));
+        assert_eq!(leet_lookup(cyrillic, 'О', 3), Some('0'));
+        assert_eq!(leet_lookup(cyrillic, 'Я', 1), None);
+        assert_eq!(leet_lookup(cyrillic, 'Я', 2), Some('R'));
+
+        for script in [Script::Greek, Script::Cyrillic] {
+            assert!(
+                script
+                    .leet()
+                    .iter()
+                    .all(|(c, _, _)| Script::of(*c) == Some(script))
+            );
+        }
+    }
+}
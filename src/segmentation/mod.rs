@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Longest dictionary word, in characters
+const MAX_WORD_LEN: usize = 8;
+
+/// Cost of a character, or run of katakana, that isn't in the dictionaries. Above
+/// every bundled word so that known words win.
+const UNKNOWN_COST: u32 = 150;
+
+/// Khmer coeng, which stacks the consonant after it below the one before it
+const COENG: char = '\u{17D2}';
+
+static DICTIONARY: LazyLock<HashMap<&'static str, u32>> = LazyLock::new(|| {
+    [
+        include_str!("../data/dictionaries/zh.txt"),
+        include_str!("../data/dictionaries/ja.txt"),
+    ]
+    .into_iter()
+    .flat_map(parse_dictionary)
+    .collect()
+});
+static THAI: LazyLock<WordList> =
+    LazyLock::new(|| parse_word_list(include_str!("../data/dictionaries/th.txt")));
+static LAO: LazyLock<WordList> =
+    LazyLock::new(|| parse_word_list(include_str!("../data/dictionaries/lo.txt")));
+static KHMER: LazyLock<WordList> =
+    LazyLock::new(|| parse_word_list(include_str!("../data/dictionaries/km.txt")));
+
+/// The words of a script written without spaces
+struct WordList {
+    words: HashSet<&'static str>,
+    /// Longest word, in characters
+    longest: usize,
+}
+
+/// The scripts written without spaces between words, each with its own segmenter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unspaced {
+    Cjk,
+    Thai,
+    Lao,
+    Khmer,
+}
+
+impl Unspaced {
+    /// Finds the unspaced script a character belongs to, None for any other character
+    fn of(c: char) -> Option<Unspaced> {
+        match c {
+            _ if is_cjk(c) => Some(Unspaced::Cjk),
+            '\u{0E01}'..='\u{0E4E}' => Some(Unspaced::Thai),
+            '\u{0E81}'..='\u{0EDF}' => Some(Unspaced::Lao),
+            '\u{1780}'..='\u{17DD}' => Some(Unspaced::Khmer),
+            _ => None,
+        }
+    }
+
+    /// Splits a run of the script into words
+    fn segment(self, run: &str) -> Vec<&str> {
+        match self {
+            Unspaced::Cjk => segment(run),
+            Unspaced::Thai => segment_with(run, &THAI),
+            Unspaced::Lao => segment_with(run, &LAO),
+            Unspaced::Khmer => segment_with(run, &KHMER),
+        }
+    }
+}
+
+/// Determines if a character is Chinese or Japanese: Han ideographs, including the
+/// iteration mark 々, hiragana and katakana
+///
+/// # Arguments
+///
+/// - `c` (`char`) - The character
+///
+/// # Returns
+///
+/// - `bool` - Whether or not the character is Chinese or Japanese
+///
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3005}' | '\u{3006}'
+        | '\u{3041}'..='\u{309F}'
+        | '\u{30A1}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}')
+}
+
+/// Determines if a grapheme can't stand apart from the one after it: Thai and Lao  
+/// vowels written before the consonant they follow in speech, e.g. "เ" in "เรา", and  
+/// Khmer graphemes ending with a coeng, e.g. "ស្" in "ស្រី"
+///
+/// # Arguments
+///
+/// - `grapheme` (`&str`) - The grapheme
+///
+/// # Returns
+///
+/// - `bool` - Whether or not the grapheme is bound to the next one
+///
+pub(crate) fn binds_forward(grapheme: &str) -> bool {
+    matches!(
+        grapheme.chars().next(),
+        Some('\u{0E40}'..='\u{0E44}' | '\u{0EC0}'..='\u{0EC4}')
+    ) || grapheme.ends_with(COENG)
+}
+
+/// Determines if a character is katakana, including the prolonged sound mark ー
+fn is_katakana(c: char) -> bool {
+    matches!(c, '\u{30A1}'..='\u{30FF}')
+}
+
+/// Splits an unspaced run of Chinese or Japanese into words, picking the split with
+/// the lowest total cost from the bundled dictionaries. Characters the dictionaries
+/// don't know become words of their own, except runs of katakana, which are kept
+/// whole as loanwords, e.g. "研究表明汉字的顺序" => ["研究", "表明", "汉字", "的", "顺序"]
+///
+/// # Arguments
+///
+/// - `run` (`&str`) - The run, all of its characters CJK
+///
+/// # Returns
+///
+/// - `Vec<&str>` - The words, in order
+///
+pub(crate) fn segment(run: &str) -> Vec<&str> {
+    let offsets: Vec<usize> = run
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([run.len()])
+        .collect();
+    let n: usize = offsets.len() - 1;
+
+    // best[i] holds the lowest cost of the first i characters and where its last word starts
+    let mut best: Vec<(u32, usize)> = vec![(u32::MAX, 0); n + 1];
+    best[0] = (0, 0);
+    for end in 1..=n {
+        for start in end.saturating_sub(MAX_WORD_LEN)..end {
+            if best[start].0 == u32::MAX {
+                continue;
+            }
+
+            let word: &str = &run[offsets[start]..offsets[end]];
+            let cost: Option<u32> = match DICTIONARY.get(word) {
+                Some(&cost) => Some(cost),
+                None if end - start == 1 || word.chars().all(is_katakana) => Some(UNKNOWN_COST),
+                None => None,
+            };
+            if let Some(cost) = cost {
+                if best[start].0 + cost < best[end].0 {
+                    best[end] = (best[start].0 + cost, start);
+                }
+            }
+        }
+    }
+
+    let mut words: Vec<&str> = Vec::new();
+    let mut end: usize = n;
+    while end > 0 {
+        let start: usize = best[end].1;
+        words.push(&run[offsets[start]..offsets[end]]);
+        end = start;
+    }
+    words.reverse();
+
+    words
+}
+
+/// Splits an unspaced run of Thai, Lao or Khmer into words found in a word list,  
+/// keeping as few letters as possible outside of known words and then using as  
+/// few words as possible. Graphemes bound to the next one are never split from it,  
+/// and neighbouring letters outside of known words become a single word, e.g.  
+/// "สวัสดีครับ" => ["สวัสดี", "ครับ"]
+///
+/// # Arguments
+///
+/// - `run` (`&str`) - The run, all of its characters in one script
+/// - `list` (`&WordList`) - The script's word list
+///
+/// # Returns
+///
+/// - `Vec<&str>` - The words, in order
+///
+fn segment_with<'a>(run: &'a str, list: &WordList) -> Vec<&'a str> {
+    // offsets of the units words may start at
+    let mut offsets: Vec<usize> = Vec::new();
+    let mut bound: bool = false;
+    for (offset, grapheme) in run.grapheme_indices(true) {
+        if !bound {
+            offsets.push(offset);
+        }
+        bound = binds_forward(grapheme);
+    }
+    offsets.push(run.len());
+    let n: usize = offsets.len() - 1;
+
+    // best[i] holds the (unknown units, words) of the first i units, where the last
+    // word starts and whether it is known
+    let mut best: Vec<((usize, usize), usize, bool)> = vec![((usize::MAX, 0), 0, false); n + 1];
+    best[0] = ((0, 0), 0, true);
+    for end in 1..=n {
+        // a unit holds at least one character, so no word spans more units than that
+        for start in end.saturating_sub(list.longest.max(1))..end {
+            let ((unknown, count), _, _) = best[start];
+            if unknown == usize::MAX {
+                continue;
+            }
+
+            let known: bool = list.words.contains(&run[offsets[start]..offsets[end]]);
+            let cost: (usize, usize) = match (known, end - start) {
+                (true, _) => (unknown, count + 1),
+                (false, 1) => (unknown + 1, count + 1),
+                (false, _) => continue,
+            };
+            if cost < best[end].0 {
+                best[end] = (cost, start, known);
+            }
+        }
+    }
+
+    let mut pieces: Vec<(usize, usize, bool)> = Vec::new();
+    let mut end: usize = n;
+    while end > 0 {
+        let (_, start, known) = best[end];
+        match pieces.last_mut() {
+            // neighbouring unknown units become a single word
+            Some((first, _, false)) if !known => *first = start,
+            _ => pieces.push((start, end, known)),
+        }
+        end = start;
+    }
+
+    pieces
+        .into_iter()
+        .rev()
+        .map(|(start, end, _)| &run[offsets[start]..offsets[end]])
+        .collect()
+}
+
+/// Splits a whitespace-delimited token at the word boundaries of its Chinese,  
+/// Japanese, Thai, Lao and Khmer runs. Punctuation stays attached to the word before  
+/// it, or to the first word, and other scripts are words of their own, e.g.  
+/// "「研究表明，汉字」" => ["「研究", "表明，", "汉字」"]
+///
+/// # Arguments
+///
+/// - `token` (`&str`) - The token
+///
+/// # Returns
+///
+/// - `Vec<&str>` - The words, which joined give back the token
+///
+pub(crate) fn split_words(token: &str) -> Vec<&str> {
+    if !token.chars().any(|c| Unspaced::of(c).is_some()) {
+        return vec![token];
+    }
+
+    // runs of each unspaced script and of everything else, as byte ranges; marks
+    // stay in the run of their base character
+    let mut runs: Vec<(usize, usize, Option<Unspaced>)> = Vec::new();
+    for (i, grapheme) in token.grapheme_indices(true) {
+        let script: Option<Unspaced> = grapheme.chars().next().and_then(Unspaced::of);
+        match runs.last_mut() {
+            Some((_, end, kind)) if *kind == script => *end = i + grapheme.len(),
+            _ => runs.push((i, i + grapheme.len(), script)),
+        }
+    }
+
+    let mut bounds: Vec<usize> = Vec::new();
+    for (start, end, script) in runs {
+        if let Some(script) = script {
+            let mut offset: usize = start;
+            for word in script.segment(&token[start..end]) {
+                bounds.push(offset);
+                offset += word.len();
+            }
+        } else if token[start..end].chars().any(char::is_alphanumeric) {
+            bounds.push(start);
+        }
+    }
+    // marks attached to punctuation make runs without words
+    if bounds.is_empty() {
+        return vec![token];
+    }
+    bounds[0] = 0;
+    bounds.push(token.len());
+
+    bounds.windows(2).map(|w| &token[w[0]..w[1]]).collect()
+}
+
+/// Parses a word list with one entry per line, skipping '#' comments
+fn parse_word_list(s: &'static str) -> WordList {
+    let words: HashSet<&'static str> = s
+        .lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .collect();
+    let longest: usize = words.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+
+    WordList { words, longest }
+}
+
+/// Parses a dictionary with one "word cost" entry per line, skipping '#' comments
+fn parse_dictionary(s: &'static str) -> impl Iterator<Item = (&'static str, u32)> {
+    s.lines()
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .filter_map(|l| {
+            let (word, cost) = l.split_once(' ')?;
+            Some((word, cost.parse().ok()?))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment() {
+        assert_eq!(
+            segment("研究表明汉字的顺序"),
+            vec!["研究", "表明", "汉字", "的", "顺序"]
+        );
+        assert_eq!(
+            segment("私は東京大学で日本語を勉強"),
+            vec!["私", "は", "東京", "大学", "で", "日本語", "を", "勉強"]
+        );
+        assert_eq!(
+            segment("コンピューターを使って"),
+            vec!["コンピューター", "を", "使", "って"]
+        );
+        assert!(segment("").is_empty());
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words("「研究表明，汉字」"),
+            vec!["「研究", "表明，", "汉字」"]
+        );
+        assert_eq!(split_words("iPhone手机"), vec!["iPhone", "手机"]);
+        assert_eq!(split_words("hello,"), vec!["hello,"]);
+        assert_eq!(split_words("(สวัสดีครับ)"), vec!["(สวัสดี", "ครับ)"]);
+        // marks attached to punctuation
+        assert_eq!(split_words(";\u{e49}"), vec![";\u{e49}"]);
+        assert_eq!(split_words("\"\u{17d2}"), vec!["\"\u{17d2}"]);
+    }
+
+    #[test]
+    fn test_segment_with() {
+        assert_eq!(
+            segment_with("ผมชอบกินข้าวเหนียว", &THAI),
+            vec!["ผม", "ชอบ", "กิน", "ข้าว", "เหนียว"]
+        );
+        assert_eq!(segment_with("ສະບາຍດີຂອບໃຈ", &LAO), vec!["ສະບາຍດີ", "ຂອບໃຈ"]);
+        assert_eq!(
+            segment_with("ខ្ញុំនិយាយភាសាខ្មែរ", &KHMER),
+            vec!["ខ្ញុំ", "និយាយ", "ភាសា", "ខ្មែរ"]
+        );
+        // unknown letters stay together, bound graphemes with their next
+        assert_eq!(segment_with("เฌฌฌ", &THAI), vec!["เฌฌฌ"]);
+        assert!(binds_forward("เ") && binds_forward("ស្") && !binds_forward("ก"));
+    }
+}
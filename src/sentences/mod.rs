@@ -0,0 +1,119 @@
+use rand::{Rng, seq::SliceRandom};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::utilities::utils::split_whitespace_runs;
+
+/// Byte range of the word inside a whitespace-delimited token, leading and trailing
+/// punctuation aside, e.g. "(well-known)," => "well-known"
+///
+/// # Arguments
+///
+/// - `token` (`&str`) - The token
+///
+/// # Returns
+///
+/// - `Option<(usize, usize)>` - Start and end of the word, None without letters or digits
+///
+fn word_range(token: &str) -> Option<(usize, usize)> {
+    let start: usize = token.find(char::is_alphanumeric)?;
+    let (last, c) = token
+        .char_indices()
+        .rev()
+        .find(|(_, c)| c.is_alphanumeric())?;
+
+    Some((start, last + c.len_utf8()))
+}
+
+/// Shuffles the order of the words in each sentence, keeping the first and last word
+/// of the sentence in place along with whitespace and punctuation, e.g.
+/// "The quick brown fox jumps." => "The brown fox quick jumps." Sentences are found
+/// with UAX #29 sentence segmentation.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The input text
+/// - `rng` (`&mut R`) - The random number generator driving the shuffle
+///
+/// # Returns
+///
+/// - `String` - The text with its words reordered
+///
+pub(crate) fn shuffle_word_order<R: Rng + ?Sized>(s: &str, rng: &mut R) -> String {
+    let mut result: String = String::with_capacity(s.len());
+
+    for sentence in s.split_sentence_bounds() {
+        let tokens: Vec<&str> = split_whitespace_runs(sentence);
+        let ranges: Vec<Option<(usize, usize)>> =
+            tokens.iter().map(|token| word_range(token)).collect();
+
+        let mut words: Vec<&str> = tokens
+            .iter()
+            .zip(&ranges)
+            .filter_map(|(token, range)| range.map(|(start, end)| &token[start..end]))
+            .collect();
+        if words.len() > 3 {
+            let last: usize = words.len() - 1;
+            words[1..last].shuffle(rng);
+        }
+
+        let mut words = words.into_iter();
+        for (token, range) in tokens.iter().zip(ranges) {
+            match range {
+                Some((start, end)) => {
+                    result.push_str(&token[..start]);
+                    result.push_str(words.next().unwrap_or(&token[start..end]));
+                    result.push_str(&token[end..]);
+                }
+                None => result.push_str(token),
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rng;
+
+    #[test]
+    fn test_shuffle_word_order() {
+        let mut rng = rng();
+        let s = "The quick brown fox jumps over the lazy dog. \"Really?\" she asked, (twice).";
+
+        let mut moved: bool = false;
+        for _ in 0..50 {
+            let result = shuffle_word_order(s, &mut rng);
+            assert!(result.starts_with("The "), "{result}");
+            assert!(
+                result.contains(" dog. \"Really?\" she asked, (twice)."),
+                "{result}"
+            );
+
+            let mut before: Vec<&str> = s.split_whitespace().collect();
+            let mut after: Vec<&str> = result.split_whitespace().collect();
+            before.sort_unstable();
+            after.sort_unstable();
+            assert_eq!(before, after);
+            moved |= result != s;
+        }
+        assert!(moved);
+
+        // punctuation stays where it was while words move
+        let s = "One, two; three and four!";
+        for _ in 0..20 {
+            let result = shuffle_word_order(s, &mut rng);
+            let punctuation: Vec<char> = result
+                .chars()
+                .filter(|c| c.is_ascii_punctuation())
+                .collect();
+            assert_eq!(punctuation, [',', ';', '!'], "{result}");
+            assert!(
+                result.starts_with("One,") && result.ends_with(" four!"),
+                "{result}"
+            );
+            assert_eq!(result.find(',').unwrap(), 3, "{result}");
+        }
+    }
+}
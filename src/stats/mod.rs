@@ -0,0 +1,49 @@
+use crate::ramp::Ramp;
+
+/// Per-segment statistics. Without a ramp the whole text is a single segment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SegmentStats {
+    /// Zero-based segment index
+    pub index: usize,
+    /// Difficulty level given by the ramp's curve, 1.0 without a ramp
+    pub level: f64,
+    /// Scramble rate applied to the segment
+    pub rate: f64,
+    /// Intensity applied to the segment
+    pub intensity: f64,
+    /// Number of words in the segment
+    pub words: usize,
+    /// Number of words that came out different from the input
+    pub scrambled: usize,
+}
+
+/// Statistics gathered by typoglycemia_with_stats()
+#[derive(Debug, Clone, Default)]
+pub struct ScrambleStats {
+    /// The ramp in effect, if any
+    pub ramp: Option<Ramp>,
+    /// Number of words in the text
+    pub words: usize,
+    /// Number of words that came out different from the input
+    pub scrambled: usize,
+    /// Breakdown per sentence or paragraph
+    pub segments: Vec<SegmentStats>,
+}
+
+impl ScrambleStats {
+    /// Share of words that came out scrambled, 0.0 for empty text
+    pub fn scrambled_ratio(&self) -> f64 {
+        if self.words == 0 {
+            0.0
+        } else {
+            self.scrambled as f64 / self.words as f64
+        }
+    }
+
+    /// Adds a finished segment to the totals
+    pub(crate) fn push(&mut self, segment: SegmentStats) {
+        self.words += segment.words;
+        self.scrambled += segment.scrambled;
+        self.segments.push(segment);
+    }
+}
@@ -0,0 +1,880 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+use yaml_rust2::Yaml;
+use yaml_rust2::parser::{Event, Parser};
+use yaml_rust2::scanner::TScalarStyle;
+
+use crate::markup::{Unit, apply_edits, scramble_run};
+
+/// One step from a document's root to a value
+#[derive(Debug, Clone, PartialEq)]
+enum PathItem {
+    /// A member of an object or mapping
+    Key(String),
+    /// An element of an array or sequence
+    Index(usize),
+}
+
+/// What a JsonPath segment selects
+#[derive(Debug, Clone)]
+enum Selector {
+    Name(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// One segment of a JsonPath, e.g. ".title", "[0]" or "..*"
+#[derive(Debug, Clone)]
+struct Segment {
+    /// Whether the segment selects at any depth, e.g. "..title"
+    descendant: bool,
+    selector: Selector,
+}
+
+impl Selector {
+    /// Determines if the selector selects a step
+    fn selects(&self, item: &PathItem) -> bool {
+        match (self, item) {
+            (Selector::Wildcard, _) => true,
+            (Selector::Name(name), PathItem::Key(key)) => name == key,
+            (Selector::Index(index), PathItem::Index(i)) => index == i,
+            _ => false,
+        }
+    }
+}
+
+/// JsonPath selects values of JSON and YAML documents with a subset of JSONPath: "$"
+/// for the root, ".name" or "['name']" for a member, "[0]" for an element, "*" or
+/// "[*]" for any member or element and ".." to select at any depth. The leading "$"
+/// may be left out, e.g.
+///
+/// ```
+/// use typoglycemia::JsonPath;
+///
+/// assert!(JsonPath::parse("$.store.book[*].title").is_some());
+/// assert!(JsonPath::parse("$..['description']").is_some());
+/// assert!(JsonPath::parse("items[0].name").is_some());
+/// assert!(JsonPath::parse("$.store[").is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// Parses a path
+    ///
+    /// # Arguments
+    ///
+    /// - `path` (`&str`) - The path, e.g. "$.fixtures[*].name" or "$..message"
+    ///
+    /// # Returns
+    ///
+    /// - `Option<JsonPath>` - The path, None if it is malformed
+    ///
+    pub fn parse(path: &str) -> Option<JsonPath> {
+        let path: &str = path.trim();
+        if path.is_empty() {
+            return None;
+        }
+
+        let mut rest: &str = path.strip_prefix('$').unwrap_or(path);
+        let mut segments: Vec<Segment> = Vec::new();
+        while !rest.is_empty() {
+            let mut descendant: bool = false;
+            if let Some(r) = rest.strip_prefix("..") {
+                descendant = true;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix('.') {
+                rest = r;
+            } else if !rest.starts_with('[') && (!segments.is_empty() || path.starts_with('$')) {
+                return None;
+            }
+
+            let selector: Selector;
+            if let Some(r) = rest.strip_prefix('[') {
+                let inner: &str;
+                if let Some(quote) = r.trim_start().chars().next().filter(|c| "'\"".contains(*c)) {
+                    let r: &str = &r.trim_start()[1..];
+                    let close: usize = r.find(quote)?;
+                    inner = &r[..close];
+                    rest = r[close + 1..].trim_start().strip_prefix(']')?;
+                    selector = Selector::Name(inner.to_string());
+                } else {
+                    let close: usize = r.find(']')?;
+                    inner = r[..close].trim();
+                    rest = &r[close + 1..];
+                    selector = match inner {
+                        "*" => Selector::Wildcard,
+                        _ => Selector::Index(inner.parse().ok()?),
+                    };
+                }
+            } else {
+                let end: usize = rest.find(['.', '[']).unwrap_or(rest.len());
+                selector = match &rest[..end] {
+                    "" => return None,
+                    "*" => Selector::Wildcard,
+                    name => Selector::Name(name.to_string()),
+                };
+                rest = &rest[end..];
+            }
+            segments.push(Segment {
+                descendant,
+                selector,
+            });
+        }
+
+        Some(JsonPath { segments })
+    }
+
+    /// Determines if the segments select exactly the value at the end of a path
+    fn matches(segments: &[Segment], path: &[PathItem]) -> bool {
+        match segments.split_first() {
+            None => path.is_empty(),
+            Some((segment, rest)) if segment.descendant => (0..path.len())
+                .any(|k| segment.selector.selects(&path[k]) && Self::matches(rest, &path[k + 1..])),
+            Some((segment, rest)) => {
+                path.first()
+                    .is_some_and(|item| segment.selector.selects(item))
+                    && Self::matches(rest, &path[1..])
+            }
+        }
+    }
+
+    /// Determines if the path selects a value or one of the objects or arrays holding it
+    fn selects(&self, path: &[PathItem]) -> bool {
+        (0..=path.len()).any(|k| Self::matches(&self.segments, &path[..k]))
+    }
+}
+
+/// StructuredOptions chooses which string values typoglycemia_json_with() and
+/// typoglycemia_yaml_with() scramble: those selected by any of the paths or held by
+/// any of the keys, or every string value when there are neither, e.g.
+///
+/// ```
+/// use typoglycemia::{JsonPath, StructuredOptions};
+///
+/// let options = StructuredOptions::new()
+///     .with_paths([JsonPath::parse("$.fixtures[*].body").unwrap()])
+///     .with_keys(["title", "description"]);
+///
+/// assert_eq!(options.keys(), ["title", "description"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StructuredOptions {
+    paths: Vec<JsonPath>,
+    keys: Vec<String>,
+}
+
+impl StructuredOptions {
+    /// Returns options that scramble every string value
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scrambles the string values selected by the paths, including those inside
+    /// selected objects and arrays
+    ///
+    /// # Arguments
+    ///
+    /// - `paths` (`I`) - The paths, e.g. [JsonPath::parse("$..message").unwrap()]
+    ///
+    pub fn with_paths<I: IntoIterator<Item = JsonPath>>(mut self, paths: I) -> Self {
+        self.paths = paths.into_iter().collect();
+        self
+    }
+
+    /// Scrambles the string values held by these keys, directly or in an array, e.g.
+    /// "tags" selects both `"tags": "one"` and `"tags": ["one", "two"]`
+    ///
+    /// # Arguments
+    ///
+    /// - `keys` (`I`) - The key names, matched exactly
+    ///
+    pub fn with_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.keys = keys.into_iter().map(|k| k.as_ref().to_string()).collect();
+        self
+    }
+
+    /// The paths selecting string values
+    pub fn paths(&self) -> &[JsonPath] {
+        &self.paths
+    }
+
+    /// The keys whose string values are scrambled
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Determines if the string value at the end of a path is scrambled
+    fn selects(&self, path: &[PathItem]) -> bool {
+        let key: Option<&String> = path.iter().rev().find_map(|item| match item {
+            PathItem::Key(key) => Some(key),
+            PathItem::Index(_) => None,
+        });
+
+        (self.paths.is_empty() && self.keys.is_empty())
+            || key.is_some_and(|key| self.keys.contains(key))
+            || self.paths.iter().any(|p| p.selects(path))
+    }
+}
+
+/// An object, mapping, array or sequence being walked
+enum Frame {
+    Mapping {
+        /// The key of the current member, once read
+        key: Option<String>,
+        /// Whether the next node is a key
+        expecting_key: bool,
+        /// Whether the mapping is itself part of a key
+        in_key: bool,
+    },
+    Sequence {
+        index: usize,
+        /// Whether the sequence is itself part of a key
+        in_key: bool,
+    },
+}
+
+/// The path from the root to the node being read
+fn path(stack: &[Frame]) -> Vec<PathItem> {
+    stack
+        .iter()
+        .map(|frame| match frame {
+            Frame::Mapping { key, .. } => PathItem::Key(key.clone().unwrap_or_default()),
+            Frame::Sequence { index, .. } => PathItem::Index(*index),
+        })
+        .collect()
+}
+
+/// Determines if the node being read is a key or part of one
+fn in_key(stack: &[Frame]) -> bool {
+    match stack.last() {
+        Some(Frame::Mapping {
+            expecting_key,
+            in_key,
+            ..
+        }) => *expecting_key || *in_key,
+        Some(Frame::Sequence { in_key, .. }) => *in_key,
+        None => false,
+    }
+}
+
+/// Moves past a finished node: from a key to its value, from a value to the next key,
+/// or to the next element
+fn next_node(stack: &mut [Frame]) {
+    match stack.last_mut() {
+        Some(Frame::Mapping {
+            key, expecting_key, ..
+        }) => {
+            if !*expecting_key {
+                *key = None;
+            }
+            *expecting_key = !*expecting_key;
+        }
+        Some(Frame::Sequence { index, .. }) => *index += 1,
+        None => {}
+    }
+}
+
+/// Escapes a grapheme for a JSON or YAML double-quoted string
+fn escape_double_quoted(grapheme: &str) -> String {
+    grapheme
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => format!("\\{c}"),
+            c if c.is_control() => format!("\\u{:04X}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Escapes a grapheme for a YAML single-quoted string
+fn escape_single_quoted(grapheme: &str) -> String {
+    grapheme.replace('\'', "''")
+}
+
+/// Decodes "\u" followed by four hex digits, and the low surrogate that follows a high one
+fn decode_unicode(rest: &str) -> Option<(usize, char)> {
+    let hex = |digits: Option<&str>| -> Option<u32> {
+        digits
+            .filter(|d| d.chars().all(|c| c.is_ascii_hexdigit()))
+            .and_then(|d| u32::from_str_radix(d, 16).ok())
+    };
+    let unit: u32 = hex(rest.get(2..6))?;
+    if (0xD800..0xDC00).contains(&unit) {
+        let low: u32 = hex(rest.get(8..12)).filter(|_| rest.get(6..8) == Some("\\u"))?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return None;
+        }
+        return char::from_u32(0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00)).map(|c| (12, c));
+    }
+
+    char::from_u32(unit).map(|c| (6, c))
+}
+
+/// Decodes a JSON escape sequence, e.g. "\n" or "é"
+///
+/// # Arguments
+///
+/// - `rest` (`&str`) - The string from the backslash on
+///
+/// # Returns
+///
+/// - `Option<(usize, char)>` - The escape's length and character, None if it is malformed
+///
+fn decode_json(rest: &str) -> Option<(usize, char)> {
+    let c: char = match rest.as_bytes().get(1)? {
+        b'"' => '"',
+        b'\\' => '\\',
+        b'/' => '/',
+        b'b' => '\u{8}',
+        b'f' => '\u{C}',
+        b'n' => '\n',
+        b'r' => '\r',
+        b't' => '\t',
+        b'u' => return decode_unicode(rest),
+        _ => return None,
+    };
+
+    Some((2, c))
+}
+
+/// Decodes a YAML double-quoted escape sequence, e.g. "\t", "\x41" or "\U0001F600".
+/// Escaped line breaks are not decoded.
+///
+/// # Arguments
+///
+/// - `rest` (`&str`) - The string from the backslash on
+///
+/// # Returns
+///
+/// - `Option<(usize, char)>` - The escape's length and character, None otherwise
+///
+fn decode_yaml(rest: &str) -> Option<(usize, char)> {
+    let hex = |length: usize| -> Option<(usize, char)> {
+        let digits: &str = rest.get(2..2 + length)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .map(|c| (2 + length, c))
+    };
+    let c: char = match rest.as_bytes().get(1)? {
+        b'0' => '\0',
+        b'a' => '\u{7}',
+        b'b' => '\u{8}',
+        b't' | b'\t' => '\t',
+        b'n' => '\n',
+        b'v' => '\u{B}',
+        b'f' => '\u{C}',
+        b'r' => '\r',
+        b'e' => '\u{1B}',
+        b' ' => ' ',
+        b'"' => '"',
+        b'/' => '/',
+        b'\\' => '\\',
+        b'N' => '\u{85}',
+        b'_' => '\u{A0}',
+        b'L' => '\u{2028}',
+        b'P' => '\u{2029}',
+        b'x' => return hex(2),
+        b'u' => return hex(4),
+        b'U' => return hex(8),
+        _ => return None,
+    };
+
+    Some((2, c))
+}
+
+/// Decodes the "''" of a YAML single-quoted string
+fn decode_single_quoted(rest: &str) -> Option<(usize, char)> {
+    rest.starts_with("''").then_some((2, '\''))
+}
+
+/// Adds the graphemes of a stretch of source text to a run
+fn push_graphemes(s: &str, range: Range<usize>, run: &mut Vec<Unit>) {
+    for (offset, grapheme) in s[range.clone()].grapheme_indices(true) {
+        run.push(Unit {
+            text: grapheme.to_string(),
+            source: range.start + offset..range.start + offset + grapheme.len(),
+        });
+    }
+}
+
+/// Joins units that decode into a single grapheme, e.g. the escapes of "\r\n" or an
+/// "e" followed by an escaped combining accent, so that each unit is one grapheme of
+/// the scrambled text
+///
+/// # Arguments
+///
+/// - `run` (`Vec<Unit>`) - The units of a run, next to each other in the source
+///
+/// # Returns
+///
+/// - `Vec<Unit>` - The units, one per grapheme
+///
+fn clustered(run: Vec<Unit>) -> Vec<Unit> {
+    let text: String = run.iter().map(|u| u.text.as_str()).collect();
+    let starts: HashSet<usize> = text.grapheme_indices(true).map(|(i, _)| i).collect();
+
+    let mut units: Vec<Unit> = Vec::new();
+    let mut offset: usize = 0;
+    for unit in run {
+        let length: usize = unit.text.len();
+        match units.last_mut() {
+            Some(last) if !starts.contains(&offset) => {
+                last.text.push_str(&unit.text);
+                last.source.end = unit.source.end;
+            }
+            _ => units.push(unit),
+        }
+        offset += length;
+    }
+
+    units
+}
+
+/// Collects the graphemes of a quoted string, each escape sequence being a single
+/// grapheme written back as it was. Escapes that cannot be decoded split the string.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `content` (`Range<usize>`) - The string between its quotes
+/// - `marker` (`char`) - The character starting an escape sequence
+/// - `decode` (`fn(&str) -> Option<(usize, char)>`) - Decodes an escape sequence
+///
+/// # Returns
+///
+/// - `Vec<Vec<Unit>>` - The runs of graphemes
+///
+fn quoted_units(
+    s: &str,
+    content: Range<usize>,
+    marker: char,
+    decode: fn(&str) -> Option<(usize, char)>,
+) -> Vec<Vec<Unit>> {
+    let mut runs: Vec<Vec<Unit>> = Vec::new();
+    let mut run: Vec<Unit> = Vec::new();
+    let mut plain: usize = content.start;
+    let mut i: usize = content.start;
+
+    while i < content.end {
+        let Some(c) = s[i..content.end].chars().next() else {
+            break;
+        };
+        if c != marker {
+            i += c.len_utf8();
+            continue;
+        }
+
+        push_graphemes(s, plain..i, &mut run);
+        match decode(&s[i..content.end]) {
+            Some((length, c)) => {
+                run.push(Unit {
+                    text: c.to_string(),
+                    source: i..i + length,
+                });
+                i += length;
+            }
+            None => {
+                runs.push(clustered(std::mem::take(&mut run)));
+                i += marker.len_utf8();
+                i += s[i..content.end].chars().next().map_or(0, char::len_utf8);
+            }
+        }
+        plain = i;
+    }
+    push_graphemes(s, plain..content.end, &mut run);
+    runs.push(clustered(run));
+
+    runs
+}
+
+/// Scrambles the string values of a JSON document, leaving keys, numbers, literals,
+/// whitespace, comments and escape sequences as they were. Malformed documents are
+/// walked as far as their structure can be followed.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `options` (`&StructuredOptions`) - Which string values to scramble
+/// - `scramble` (`F`) - Scrambles plain text
+///
+/// # Returns
+///
+/// - `String` - The document with its string values scrambled
+///
+pub(crate) fn scramble_json<F: FnMut(&str) -> String>(
+    s: &str,
+    options: &StructuredOptions,
+    mut scramble: F,
+) -> String {
+    let bytes: &[u8] = s.as_bytes();
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    let mut i: usize = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => stack.push(Frame::Mapping {
+                key: None,
+                expecting_key: true,
+                in_key: false,
+            }),
+            b'[' => stack.push(Frame::Sequence {
+                index: 0,
+                in_key: false,
+            }),
+            b'}' | b']' => {
+                stack.pop();
+            }
+            b',' => match stack.last_mut() {
+                Some(Frame::Mapping {
+                    key, expecting_key, ..
+                }) => {
+                    *key = None;
+                    *expecting_key = true;
+                }
+                Some(Frame::Sequence { index, .. }) => *index += 1,
+                None => {}
+            },
+            b':' => {
+                if let Some(Frame::Mapping { expecting_key, .. }) = stack.last_mut() {
+                    *expecting_key = false;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = s[i..].find('\n').map_or(s.len(), |p| i + p);
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = s[i + 2..].find("*/").map_or(s.len(), |p| i + 2 + p + 2);
+                continue;
+            }
+            b'"' => {
+                // escaped characters are skipped byte by byte, which never lands on a quote
+                let mut end: usize = i + 1;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let content: Range<usize> = i + 1..end.min(s.len());
+                let runs: Vec<Vec<Unit>> = quoted_units(s, content, '\\', decode_json);
+                let selected: bool = options.selects(&path(&stack));
+
+                match stack.last_mut() {
+                    Some(Frame::Mapping {
+                        key,
+                        expecting_key: true,
+                        ..
+                    }) => *key = Some(runs.iter().flatten().map(|u| u.text.as_str()).collect()),
+                    _ if selected => {
+                        for units in &runs {
+                            scramble_run(s, units, &mut scramble, escape_double_quoted, &mut edits);
+                        }
+                    }
+                    _ => {}
+                }
+                i = end + 1;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    apply_edits(s, edits)
+}
+
+/// Finds where the value of a plain or block scalar is written, any run of whitespace
+/// in the value matching any run in the source, as lines are folded and indented
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `from` (`usize`) - Byte offset where the scalar's content starts
+/// - `value` (`&str`) - The scalar's value
+///
+/// # Returns
+///
+/// - `Option<Range<usize>>` - The content's byte range, None if it cannot be found
+///
+fn written_range(s: &str, from: usize, value: &str) -> Option<Range<usize>> {
+    let leading = |i: usize| s[i..].len() - s[i..].trim_start().len();
+    let start: usize = from + leading(from);
+    let mut i: usize = start;
+
+    let mut chars = value.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match leading(i) {
+                0 => return None,
+                whitespace => i += whitespace,
+            }
+        } else if s[i..].starts_with(c) {
+            i += c.len_utf8();
+        } else {
+            return None;
+        }
+    }
+
+    (start < i).then_some(start..i)
+}
+
+/// Collects the graphemes of a YAML scalar as written, escape sequences included
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `start` (`usize`) - Byte offset where the parser reported the scalar
+/// - `value` (`&str`) - The scalar's value
+/// - `style` (`TScalarStyle`) - How the scalar is written
+///
+/// # Returns
+///
+/// - `Vec<Vec<Unit>>` - The runs of graphemes
+///
+fn scalar_units(s: &str, start: usize, value: &str, style: TScalarStyle) -> Vec<Vec<Unit>> {
+    let bytes: &[u8] = s.as_bytes();
+    let mut end: usize = start + 1;
+    match style {
+        TScalarStyle::DoubleQuoted => {
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            let content: Range<usize> = start + 1..end.min(s.len());
+            quoted_units(s, content, '\\', decode_yaml)
+        }
+        TScalarStyle::SingleQuoted => {
+            while end < bytes.len() && (bytes[end] != b'\'' || bytes.get(end + 1) == Some(&b'\'')) {
+                end += if bytes[end] == b'\'' { 2 } else { 1 };
+            }
+            let content: Range<usize> = start + 1..end.min(s.len());
+            quoted_units(s, content, '\'', decode_single_quoted)
+        }
+        _ => {
+            let mut run: Vec<Unit> = Vec::new();
+            if let Some(range) = written_range(s, start, value) {
+                push_graphemes(s, range, &mut run);
+            }
+            vec![run]
+        }
+    }
+}
+
+/// Scrambles the string values of a YAML document, leaving keys, numbers, booleans,
+/// nulls, comments, anchors, tags, indentation and escape sequences as they were.
+/// Strings tagged as another type are left alone, and a document that cannot be
+/// parsed is returned unchanged.
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document, or a stream of documents
+/// - `options` (`&StructuredOptions`) - Which string values to scramble
+/// - `scramble` (`F`) - Scrambles plain text
+///
+/// # Returns
+///
+/// - `String` - The document with its string values scrambled
+///
+pub(crate) fn scramble_yaml<F: FnMut(&str) -> String>(
+    s: &str,
+    options: &StructuredOptions,
+    mut scramble: F,
+) -> String {
+    // the parser counts characters, not bytes
+    let offsets: Vec<usize> = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(s.len()))
+        .collect();
+    let mut parser = Parser::new_from_str(s);
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+
+    loop {
+        let Ok((event, marker)) = parser.next_token() else {
+            return s.to_string();
+        };
+        match event {
+            Event::StreamEnd => break,
+            Event::MappingStart(..) => {
+                let in_key: bool = in_key(&stack);
+                stack.push(Frame::Mapping {
+                    key: None,
+                    expecting_key: true,
+                    in_key,
+                });
+            }
+            Event::SequenceStart(..) => {
+                let in_key: bool = in_key(&stack);
+                stack.push(Frame::Sequence { index: 0, in_key });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                stack.pop();
+                next_node(&mut stack);
+            }
+            Event::Alias(_) => next_node(&mut stack),
+            Event::Scalar(value, style, _, tag) => {
+                if let Some(Frame::Mapping {
+                    key,
+                    expecting_key: true,
+                    in_key: false,
+                }) = stack.last_mut()
+                {
+                    *key = Some(value);
+                    next_node(&mut stack);
+                    continue;
+                }
+
+                let string: bool = match &tag {
+                    Some(tag) => tag.suffix == "str",
+                    None => {
+                        style != TScalarStyle::Plain
+                            || matches!(Yaml::from_str(&value), Yaml::String(_))
+                    }
+                };
+                let Some(&start) = offsets.get(marker.index()) else {
+                    return s.to_string();
+                };
+                if string && !in_key(&stack) && options.selects(&path(&stack)) {
+                    let runs: Vec<Vec<Unit>> = scalar_units(s, start, &value, style);
+                    let escape: fn(&str) -> String = match style {
+                        TScalarStyle::DoubleQuoted => escape_double_quoted,
+                        TScalarStyle::SingleQuoted => escape_single_quoted,
+                        _ => str::to_string,
+                    };
+                    for units in &runs {
+                        scramble_run(s, units, &mut scramble, escape, &mut edits);
+                    }
+                }
+                next_node(&mut stack);
+            }
+            _ => {}
+        }
+    }
+
+    apply_edits(s, edits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utilities::utils::split_whitespace_runs;
+
+    fn reverse(t: &str) -> String {
+        split_whitespace_runs(t)
+            .into_iter()
+            .map(|w| {
+                if w.trim().is_empty() {
+                    w.to_string()
+                } else {
+                    w.graphemes(true).rev().collect::<String>()
+                }
+            })
+            .collect()
+    }
+
+    fn key(k: &str) -> PathItem {
+        PathItem::Key(k.to_string())
+    }
+
+    #[test]
+    fn test_json_path() {
+        let path = [key("store"), key("book"), PathItem::Index(2), key("title")];
+        for selector in [
+            "$",
+            "$.store",
+            "$.store.book[*].title",
+            "store.book[2]",
+            "$['store']['book'][2]['title']",
+            "$..title",
+            "$..book..*",
+            "$.*.book",
+        ] {
+            let parsed = JsonPath::parse(selector).unwrap();
+            assert!(parsed.selects(&path), "{selector}");
+        }
+        for selector in ["$.book", "$.store.book[0]", "$..author", "$.*.title"] {
+            let parsed = JsonPath::parse(selector).unwrap();
+            assert!(!parsed.selects(&path), "{selector}");
+        }
+        for malformed in ["", "$.", "$.store[", "$[x]", "$..", "$store"] {
+            assert!(JsonPath::parse(malformed).is_none(), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn test_scramble_json() {
+        let s = "{\n  \"title\": \"Hello world\", // comment\n  \"count\": 12,\n  \
+                 \"tags\": [\"one two\", \"caf\\u00e9s \\\"quoted\\\"\"],\n  \
+                 \"nested\": {\"title\": \"Deep text\", \"ok\": true},\n  \
+                 \"lines\": \"Paragraph number\\r\\nsecondline cafe\\u0301s\"\n}";
+
+        assert_eq!(
+            scramble_json(s, &StructuredOptions::new(), reverse),
+            s.replace("Hello world", "olleH dlrow")
+                .replace("one two", "eno owt")
+                .replace("caf\\u00e9s \\\"quoted\\\"", "s\\u00e9fac \\\"detouq\\\"")
+                .replace("Deep text", "peeD txet")
+                .replace(
+                    "Paragraph number\\r\\nsecondline cafe\\u0301s",
+                    "hpargaraP rebmun\\r\\nenildnoces se\\u0301fac"
+                )
+        );
+
+        let options = StructuredOptions::new()
+            .with_paths([JsonPath::parse("$.nested").unwrap()])
+            .with_keys(["tags"]);
+        assert_eq!(
+            scramble_json(s, &options, reverse),
+            s.replace("one two", "eno owt")
+                .replace("caf\\u00e9s \\\"quoted\\\"", "s\\u00e9fac \\\"detouq\\\"")
+                .replace("Deep text", "peeD txet")
+        );
+    }
+
+    #[test]
+    fn test_scramble_yaml() {
+        let s = "# fixture\ntitle: Hello world  # comment\ncount: 12\nenabled: true\n\
+                 quoted: \"say \\\"hi\\\" now\"\nsingle: 'it''s here'\n\
+                 tags:\n  - one two\n  - !!str 42\n\
+                 body: |\n  First line\n  second line\n\
+                 folded: plain words\n  continue here\n\
+                 ? [complex, key]\n: value text\n";
+
+        assert_eq!(
+            scramble_yaml(s, &StructuredOptions::new(), reverse),
+            s.replace("Hello world", "olleH dlrow")
+                .replace("say \\\"hi\\\" now", "yas \\\"ih\\\" won")
+                .replace("it''s here", "s''ti ereh")
+                .replace("one two", "eno owt")
+                .replace("First line\n  second line", "tsriF enil\n  dnoces enil")
+                .replace(
+                    "plain words\n  continue here",
+                    "nialp sdrow\n  eunitnoc ereh"
+                )
+                .replace("value text", "eulav txet")
+        );
+
+        let options = StructuredOptions::new().with_paths([JsonPath::parse("$.tags[0]").unwrap()]);
+        assert_eq!(
+            scramble_yaml(s, &options, reverse),
+            s.replace("one two", "eno owt")
+        );
+
+        // malformed documents are left alone
+        let s = "key: [unclosed\nother: value";
+        assert_eq!(scramble_yaml(s, &StructuredOptions::new(), reverse), s);
+    }
+}
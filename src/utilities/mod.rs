@@ -18,28 +18,272 @@ const LATIN_2: Range<usize> = 248..256;
 /// ASCII ƒ, Š, Œ, Ž, š, œ, ž, Ÿ
 const MISC_CHARS: &[&i32] = &[&131, &138, &140, &142, &154, &156, &158, &159];
 
+/// Greek Ά - ϻ
+const GREEK: Range<usize> = 902..1020;
+
+/// Greek Extended ἀ - ῼ
+const GREEK_EXTENDED: Range<usize> = 7936..8189;
+
+/// Cyrillic Ѐ - ӿ
+const CYRILLIC: Range<usize> = 1024..1280;
+
+/// Hebrew א - ת, plus the Yiddish ligatures װ - ײ
+const HEBREW: Range<usize> = 1488..1523;
+
+/// Arabic ؠ - ي, including the tatweel
+const ARABIC: Range<usize> = 1568..1611;
+
+/// Arabic ٮ - ۓ, the letters added for Persian, Urdu and other languages
+const ARABIC_EXTENDED: Range<usize> = 1646..1748;
+
+/// Arabic-Indic digits ٠ - ٩
+const ARABIC_INDIC_DIGITS: Range<usize> = 1632..1642;
+
+/// Extended Arabic-Indic digits ۰ - ۹
+const EXTENDED_ARABIC_INDIC_DIGITS: Range<usize> = 1776..1786;
+
+/// Devanagari ऀ - ॣ, up to the dandas
+const DEVANAGARI: Range<usize> = 2304..2404;
+
+/// Devanagari digits ० - ॿ, then Bengali, Gurmukhi, Gujarati, Oriya, Tamil, Telugu,
+/// Kannada and Malayalam
+const INDIC: Range<usize> = 2406..3456;
+
+/// Thai ก - ฺ, ฿ aside
+const THAI: Range<usize> = 3585..3643;
+
+/// Thai vowels เ - ๅ, written before the consonant, and mai yamok ๆ
+const THAI_VOWELS: Range<usize> = 3648..3655;
+
+/// Thai digits ๐ - ๙
+const THAI_DIGITS: Range<usize> = 3664..3674;
+
+/// Lao ກ - ຽ
+const LAO: Range<usize> = 3713..3774;
+
+/// Lao vowels ເ - ໄ, written before the consonant, and ko la ໆ
+const LAO_VOWELS: Range<usize> = 3776..3783;
+
+/// Lao digits ໐ - ໙ and the ligatures ໜ - ໟ
+const LAO_DIGITS: Range<usize> = 3792..3808;
+
+/// Khmer ក - ឳ
+const KHMER: Range<usize> = 6016..6068;
+
+/// Khmer digits ០ - ៩
+const KHMER_DIGITS: Range<usize> = 6112..6122;
+
+/// Hangul syllables 가 - 힣
+const HANGUL: Range<usize> = 44032..55204;
+
+/// Hiragana ぁ - ゟ and katakana ゠ - ヿ
+const KANA: Range<usize> = 12353..12544;
+
+/// CJK iteration marks 々 and 〆
+const CJK_MARKS: Range<usize> = 12293..12295;
+
+/// CJK Unified Ideographs Extension A 㐀 - 䶿
+const CJK_EXTENSION_A: Range<usize> = 13312..19904;
+
+/// CJK Unified Ideographs 一 - 鿿
+const CJK_IDEOGRAPHS: Range<usize> = 19968..40960;
+
+/// CJK Compatibility Ideographs 豈 - 﫿
+const CJK_COMPATIBILITY: Range<usize> = 63744..64256;
+
+/// Bidi formatting characters: ALM, LRM, RLM, the embeddings and overrides, and the isolates
+const BIDI_CONTROLS: &[char] = &[
+    '\u{061C}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}',
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+];
+
+/// Marks written inside abbreviations and acronyms: Hebrew geresh and gershayim, and the
+/// ASCII quote often typed for the latter, e.g. צה"ל
+const WORD_INTERNAL_MARKS: &[char] = &['\u{05F3}', '\u{05F4}', '"'];
+
 pub mod utils {
     use atoi::atoi;
-    use rand::{rng, seq::SliceRandom};
-    use unicode_segmentation::UnicodeSegmentation;
+    use std::collections::HashSet;
+    use std::ops::Range;
+    use std::sync::LazyLock;
 
-    use crate::utilities::{LATIN_1, LATIN_2, LCASE_AZ, MISC_CHARS, UCASE_AZ, ZERO_TO_NINE};
+    use rand::{Rng, rng, seq::SliceRandom, seq::index::sample};
+    use unicode_normalization::UnicodeNormalization;
+    use unicode_segmentation::UnicodeSegmentation;
 
-    /// Get usize vector of valid ASCII characters
+    use crate::hangul::Syllable;
+    use crate::language::APOSTROPHES;
+    use crate::numeric::is_numeric_token;
+    use crate::scrambler::{
+        CasePolicy, HangulMode, IndicMode, NumericPolicy, Scrambler, ShuffleMode, SyllableMode,
+    };
+    use crate::script::ends_with_virama;
+    use crate::segmentation::{binds_forward, is_cjk};
+    use crate::utilities::{
+        ARABIC, ARABIC_EXTENDED, ARABIC_INDIC_DIGITS, BIDI_CONTROLS, CJK_COMPATIBILITY,
+        CJK_EXTENSION_A, CJK_IDEOGRAPHS, CJK_MARKS, CYRILLIC, DEVANAGARI,
+        EXTENDED_ARABIC_INDIC_DIGITS, GREEK, GREEK_EXTENDED, HANGUL, HEBREW, INDIC, KANA, KHMER,
+        KHMER_DIGITS, LAO, LAO_DIGITS, LAO_VOWELS, LATIN_1, LATIN_2, LCASE_AZ, MISC_CHARS, THAI,
+        THAI_DIGITS, THAI_VOWELS, UCASE_AZ, WORD_INTERNAL_MARKS, ZERO_TO_NINE,
+    };
+
+    /// Number of candidate orderings weighed by the readable and hard shuffle modes
+    const CANDIDATE_SHUFFLES: usize = 32;
+
+    /// Hebrew maqaf, the hyphen joining Hebrew words
+    const MAQAF: char = '\u{05BE}';
+
+    static VALID_CHARS: LazyLock<HashSet<usize>> = LazyLock::new(get_all_valid_ascii_chars);
+
+    /// Get usize set of valid ASCII characters, plus the Greek, Cyrillic, Hebrew and
+    /// Arabic letters, the Arabic-Indic digits, the Indic scripts, Thai, Lao and Khmer,
+    /// the Hangul syllables and the CJK ideographs and kana
     ///
     /// # Returns
     ///
-    /// - `Vec<usize>` - Describe the return value.
+    /// - `HashSet<usize>` - Describe the return value.
     ///
-    fn get_all_valid_ascii_chars() -> Vec<usize> {
+    fn get_all_valid_ascii_chars() -> HashSet<usize> {
         let zero_to_nine: Vec<usize> = ZERO_TO_NINE.collect();
         let ucase_az: Vec<usize> = UCASE_AZ.collect();
         let lcase_az: Vec<usize> = LCASE_AZ.collect();
         let latin1: Vec<usize> = LATIN_1.collect();
         let latin2: Vec<usize> = LATIN_2.collect();
         let misc_chars: Vec<usize> = MISC_CHARS.iter().map(|&x| *x as usize).collect();
+        let greek: Vec<usize> = GREEK.chain(GREEK_EXTENDED).collect();
+        let cyrillic: Vec<usize> = CYRILLIC.collect();
+        let hebrew: Vec<usize> = HEBREW.collect();
+        let arabic: Vec<usize> = ARABIC
+            .chain(ARABIC_EXTENDED)
+            .chain(ARABIC_INDIC_DIGITS)
+            .chain(EXTENDED_ARABIC_INDIC_DIGITS)
+            .collect();
+        let indic: Vec<usize> = DEVANAGARI.chain(INDIC).collect();
+        let southeast_asian: Vec<usize> = THAI
+            .chain(THAI_VOWELS)
+            .chain(THAI_DIGITS)
+            .chain(LAO)
+            .chain(LAO_VOWELS)
+            .chain(LAO_DIGITS)
+            .chain(KHMER)
+            .chain(KHMER_DIGITS)
+            .collect();
+        let hangul: Vec<usize> = HANGUL.collect();
+        let cjk: Vec<usize> = KANA
+            .chain(CJK_MARKS)
+            .chain(CJK_EXTENSION_A)
+            .chain(CJK_IDEOGRAPHS)
+            .chain(CJK_COMPATIBILITY)
+            .collect();
+
+        [
+            zero_to_nine,
+            ucase_az,
+            lcase_az,
+            latin1,
+            latin2,
+            misc_chars,
+            greek,
+            cyrillic,
+            hebrew,
+            arabic,
+            indic,
+            southeast_asian,
+            hangul,
+            cjk,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
 
-        [zero_to_nine, ucase_az, lcase_az, latin1, latin2, misc_chars].concat()
+    /// Determines if a grapheme keeps its position inside a word while the letters around  
+    /// it are shuffled: bidi formatting characters and the marks of abbreviations, e.g.  
+    /// the gershayim in "צה״ל"
+    ///
+    /// # Arguments
+    ///
+    /// - `grapheme` (`&str`) - The grapheme
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not the grapheme stays in place
+    ///
+    fn is_pinned(grapheme: &str) -> bool {
+        grapheme
+            .chars()
+            .next()
+            .is_some_and(|c| BIDI_CONTROLS.contains(&c) || WORD_INTERNAL_MARKS.contains(&c))
+    }
+
+    /// Determines if a word is an all-caps acronym of at least two letters, digits and  
+    /// periods aside and a trailing plural "s" allowed, e.g. "NASA", "U.S.", "HTML5" or "FAQs"
+    ///
+    /// # Arguments
+    ///
+    /// - `word` (`&str`) - The token
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not the word is an acronym
+    ///
+    pub(crate) fn is_acronym(word: &str) -> bool {
+        let core: &str = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let core: &str = core
+            .strip_suffix("'s")
+            .or_else(|| core.strip_suffix('s'))
+            .unwrap_or(core);
+        let letters: Vec<char> = core.chars().filter(|c| c.is_alphabetic()).collect();
+
+        letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase())
+    }
+
+    /// Puts the capitals of the original word back at their positions in the scrambled  
+    /// word, e.g. "McDonald" and "MDcnoald" => "MdCnoald". Graphemes whose case  
+    /// mapping would change their length, such as "ß", are left as they are.
+    ///
+    /// # Arguments
+    ///
+    /// - `original` (`&str`) - The word before scrambling
+    /// - `scrambled` (`&str`) - The same graphemes in their scrambled order
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The scrambled word with the original case pattern
+    ///
+    pub(crate) fn restore_case(original: &str, scrambled: &str) -> String {
+        original
+            .graphemes(true)
+            .zip(scrambled.graphemes(true))
+            .map(|(before, after)| {
+                let upper: bool = before.chars().any(char::is_uppercase);
+                let lower: bool = before.chars().any(char::is_lowercase);
+                let recased: String = match (upper, lower) {
+                    (true, false) => after.to_uppercase(),
+                    (false, true) => after.to_lowercase(),
+                    _ => after.to_owned(),
+                };
+                match recased.chars().count() == after.chars().count() {
+                    true => recased,
+                    false => after.to_owned(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the code point of a grapheme's base character, so that "é" written as  
+    /// U+00E9 (NFC) and as "e" + U+0301 (NFD) are judged the same way
+    ///
+    /// # Arguments
+    ///
+    /// - `grapheme` (`&str`) - The grapheme
+    ///
+    /// # Returns
+    ///
+    /// - `usize` - The composed base character's code point, 0 for an empty string
+    ///
+    fn base_char(grapheme: &str) -> usize {
+        grapheme.nfc().next().map_or(0, |c| c as usize)
     }
 
     /// Returns the index of the last allowable ASCII character in a word, per the allowance configuration  
@@ -47,22 +291,20 @@ pub mod utils {
     /// # Arguments
     ///
     /// - `s` (`&str`) - The input string
-    /// - `valid_chars` (`&[usize]`) - usize array of valid ASCII characters
+    /// - `valid_chars` (`&HashSet<usize>`) - usize set of valid ASCII characters
     ///
     /// # Returns
     ///
     /// - `usize` - The first valid start index
     ///
-    fn get_valid_end_index(s: &str, valid_chars: &[usize]) -> usize {
+    fn get_valid_end_index(s: &str, valid_chars: &HashSet<usize>) -> usize {
         let mut ret: usize = 0;
         let trimmed: &str = s.trim();
 
         let g: Vec<&str> = trimmed.graphemes(true).collect::<Vec<&str>>();
 
         for (index, character) in g.iter().rev().enumerate() {
-            let bytes: &[u8] = character.as_bytes();
-            let first_byte: usize = bytes[0] as usize;
-            if character.is_ascii() && valid_chars.contains(&first_byte) {
+            if valid_chars.contains(&base_char(character)) {
                 ret = index;
                 break;
             }
@@ -76,21 +318,19 @@ pub mod utils {
     /// # Arguments
     ///
     /// - `s` (`&str`) - The input string
-    /// - `valid_chars` (`&[usize]`) - vector comprisesd of usize valid ASCII characters
+    /// - `valid_chars` (`&HashSet<usize>`) - set comprisesd of usize valid ASCII characters
     ///
     /// # Returns
     ///
     /// - `usize` - The first valid start index
-    fn get_valid_start_index(s: &str, valid_chars: &[usize]) -> usize {
+    fn get_valid_start_index(s: &str, valid_chars: &HashSet<usize>) -> usize {
         let mut ret: usize = 0;
         let trimmed: &str = s.trim();
 
         let g: Vec<&str> = trimmed.graphemes(true).collect::<Vec<&str>>();
 
         for (index, character) in g.iter().enumerate() {
-            let bytes: &[u8] = character.as_bytes();
-            let first_byte: usize = bytes[0] as usize;
-            if character.is_ascii() && valid_chars.contains(&first_byte) {
+            if valid_chars.contains(&base_char(character)) {
                 ret = index;
                 break;
             }
@@ -99,7 +339,7 @@ pub mod utils {
         ret
     }
 
-    /// Determines if a word contains apostrophes
+    /// Determines if a word contains apostrophes, typewriter or typographic
     ///
     /// # Arguments
     ///
@@ -112,7 +352,7 @@ pub mod utils {
     fn has_apostrophes(s: &str) -> bool {
         let g: Vec<&str> = s.graphemes(true).collect::<Vec<&str>>();
         let mut it: std::slice::Iter<'_, &str> = g.iter();
-        let index: Option<usize> = it.position(|&r| r == "'");
+        let index: Option<usize> = it.position(|&r| r == "'" || r == "\u{2019}");
 
         index.is_some()
     }
@@ -135,25 +375,171 @@ pub mod utils {
         index.is_some()
     }
 
-    /// Each part of the word between apostrophes will be typoglycemified and rejoined with an apostrophe, e.g.  
+    /// Each part of the word between apostrophes will be typoglycemified and rejoined with the same apostrophe, e.g.  
     /// "Principal O'Shag'Hennessey" => "Pirncaipl O'Shag'Hesnneesy" // Mr. Garvey
     ///
     /// # Arguments
     ///
     /// - `s` (`&str`) - The word containing apostrophes
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
     ///
     /// # Returns
     ///
     /// - `String` - The modified string with portions scrambled
     ///
-    fn handle_apostrophe_string(s: &str) -> String {
-        let mut v: Vec<String> = Vec::new();
-        let it: std::str::Split<'_, &str> = s.split("'");
-        for part in it {
-            v.push(scramble_word(part.to_owned()));
+    fn handle_apostrophe_string<R: Rng + ?Sized>(
+        s: &str,
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        let mut v: String = String::with_capacity(s.len());
+        let parts: Vec<&str> = s.split(APOSTROPHES).collect();
+        let marks: Vec<char> = s.chars().filter(|c| APOSTROPHES.contains(c)).collect();
+        for (index, part) in parts.iter().enumerate() {
+            let before: Option<char> = index.checked_sub(1).map(|i| marks[i]);
+            let after: Option<char> = marks.get(index).copied();
+            v.push_str(&scramble_part(
+                part,
+                (before, after),
+                scrambler,
+                intensity,
+                rng,
+            ));
+            v.extend(after);
+        }
+
+        v
+    }
+
+    /// Splits an identifier or hashtag into its components at underscores and case  
+    /// changes, keeping the underscores as components of their own so that joining them  
+    /// gives back the word, e.g. "XMLHttpRequest" => ["XML", "Http", "Request"] and  
+    /// "max_retry_count" => ["max", "_", "retry", "_", "count"]
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<&str>` - The components, in order
+    ///
+    fn split_subwords(s: &str) -> Vec<&str> {
+        let g: Vec<(usize, &str)> = s.grapheme_indices(true).collect();
+        let is_upper = |i: usize| g[i].1.starts_with(char::is_uppercase);
+        let is_lower = |i: usize| {
+            g[i].1
+                .starts_with(|c: char| c.is_lowercase() || c.is_numeric())
+        };
+
+        let mut parts: Vec<&str> = Vec::new();
+        let mut start: usize = 0;
+        for i in 1..g.len() {
+            let underscore: bool = (g[i].1 == "_") != (g[i - 1].1 == "_");
+            let camel: bool = is_lower(i - 1) && is_upper(i);
+            let acronym: bool =
+                is_upper(i - 1) && is_upper(i) && i + 1 < g.len() && is_lower(i + 1);
+            if underscore || camel || acronym {
+                parts.push(&s[start..g[i].0]);
+                start = g[i].0;
+            }
         }
+        if start < s.len() {
+            parts.push(&s[start..]);
+        }
+
+        parts
+    }
 
-        v.join("'")
+    /// Each component of a camelCase, PascalCase or snake_case word is typoglycemified  
+    /// with its own anchors and the components are rejoined as they were, e.g.  
+    /// "getUserName" => "getUesrNmae"
+    ///
+    /// # Arguments
+    ///
+    /// - `parts` (`&[&str]`) - The components, see split_subwords()
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The rejoined word with each component scrambled
+    ///
+    fn handle_subword_string<R: Rng + ?Sized>(
+        parts: &[&str],
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        parts
+            .iter()
+            .map(|part| scramble_word_with(part.to_string(), scrambler, intensity, rng))
+            .collect()
+    }
+
+    /// Shuffles the letters of a mixed alphanumeric word among themselves while its  
+    /// digits stay where they are, e.g. "Covid19-related" => "Cviod19-rletaed": the  
+    /// letters are scrambled as a word of their own and put back around the digits
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word containing digits
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The word with its letters scrambled
+    ///
+    fn handle_alphanumeric_string<R: Rng + ?Sized>(
+        s: &str,
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        let is_digit = |g: &str| g.starts_with(|c: char| c.is_numeric());
+        let g: Vec<&str> = s.graphemes(true).collect();
+        let letters: String = g.iter().filter(|x| !is_digit(x)).copied().collect();
+        let scrambled: String = scramble_word_with(letters, scrambler, intensity, rng);
+
+        let mut scrambled_letters = scrambled.graphemes(true);
+        g.iter()
+            .map(|x| match is_digit(x) {
+                true => *x,
+                false => scrambled_letters.next().unwrap_or(x),
+            })
+            .collect()
+    }
+
+    /// Each part of the word between Hebrew maqafs will be typoglycemified on its own and  
+    /// rejoined with the maqaf, the same as hyphenated words
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word containing maqafs
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The rejoined string with portions scrambled
+    ///
+    fn handle_maqaf_string<R: Rng + ?Sized>(
+        s: &str,
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        s.split(MAQAF)
+            .map(|part| scramble_word_with(part.to_owned(), scrambler, intensity, rng))
+            .collect::<Vec<String>>()
+            .join(&MAQAF.to_string())
     }
 
     /// Each part of the word between the hyphens will be typoglycemified and rejoined with hyphens, e.g.  
@@ -162,16 +548,32 @@ pub mod utils {
     /// # Arguments
     ///
     /// - `s` (`&str`) - The hyphenated word
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
     ///
     /// # Returns
     ///
     /// - `String` - The re-hyphenated string with portions scrambled
     ///
-    fn handle_hyphenated_string(s: &str) -> String {
+    fn handle_hyphenated_string<R: Rng + ?Sized>(
+        s: &str,
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
         let mut coll: Vec<String> = Vec::new();
-        let it: std::str::Split<'_, &str> = s.split("-");
-        for part in it {
-            coll.push(scramble_word(part.to_owned()));
+        let parts: Vec<&str> = s.split("-").collect();
+        for (index, part) in parts.iter().enumerate() {
+            let before: Option<char> = (index > 0).then_some('-');
+            let after: Option<char> = (index + 1 < parts.len()).then_some('-');
+            coll.push(scramble_part(
+                part,
+                (before, after),
+                scrambler,
+                intensity,
+                rng,
+            ));
         }
 
         coll.join("-")
@@ -183,36 +585,437 @@ pub mod utils {
     /// # Arguments
     ///
     /// - `s` (`&str`) - The hyphenated word
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
     ///
     /// # Returns
     ///
     /// - `String` - The re-joined string
     ///
-    fn handle_apostrophe_and_hyphenated_string(s: &str) -> String {
+    fn handle_apostrophe_and_hyphenated_string<R: Rng + ?Sized>(
+        s: &str,
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
         let mut v1: Vec<String> = Vec::new();
-        let mut v2: Vec<String> = Vec::new();
-
-        let it: std::str::Split<'_, &str> = s.split("-");
-        for i in it {
-            let st = i.split("'");
-            for s in st {
-                v2.push(scramble_word(s.to_owned()));
+        let mut v2: String = String::new();
+
+        let words: Vec<&str> = s.split("-").collect();
+        for (i, word) in words.iter().enumerate() {
+            let parts: Vec<&str> = word.split(APOSTROPHES).collect();
+            let marks: Vec<char> = word.chars().filter(|c| APOSTROPHES.contains(c)).collect();
+            for (j, part) in parts.iter().enumerate() {
+                let before: Option<char> = match j {
+                    0 => (i > 0).then_some('-'),
+                    _ => Some(marks[j - 1]),
+                };
+                let after: Option<char> = match marks.get(j) {
+                    Some(&mark) => Some(mark),
+                    None => (i + 1 < words.len()).then_some('-'),
+                };
+                v2.push_str(&scramble_part(
+                    part,
+                    (before, after),
+                    scrambler,
+                    intensity,
+                    rng,
+                ));
+                v2.extend(marks.get(j));
             }
-            v1.push(v2.join("'"));
-            v2.clear();
+            v1.push(std::mem::take(&mut v2));
         }
 
         v1.join("-")
     }
 
+    /// Scrambles one part of an apostrophe or hyphen joined word, leaving the language's  
+    /// elisions and clitics untouched, e.g. French "jusqu" in "jusqu'ici" and "elles" in  
+    /// "disent-elles"
+    ///
+    /// # Arguments
+    ///
+    /// - `part` (`&str`) - The part between the joiners
+    /// - `joiners` (`(Option<char>, Option<char>)`) - The apostrophe or hyphen before and after the part
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The part, scrambled unless it is an elision or clitic
+    ///
+    fn scramble_part<R: Rng + ?Sized>(
+        part: &str,
+        joiners: (Option<char>, Option<char>),
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        let language = scrambler.language();
+        let lower: String = part.to_lowercase();
+        // elisions and clitics are listed with the typewriter apostrophe
+        let joiners = (
+            joiners
+                .0
+                .map(|j| if APOSTROPHES.contains(&j) { '\'' } else { j }),
+            joiners
+                .1
+                .map(|j| if APOSTROPHES.contains(&j) { '\'' } else { j }),
+        );
+
+        let elided: bool = joiners.1.is_some_and(|j| {
+            language
+                .elisions()
+                .iter()
+                .any(|e| e.strip_suffix(j) == Some(lower.as_str()))
+        });
+        let clitic: bool = joiners.0.is_some_and(|j| {
+            language
+                .clitics()
+                .iter()
+                .any(|c| c.strip_prefix(j) == Some(lower.as_str()))
+        });
+
+        if elided || clitic {
+            part.to_owned()
+        } else {
+            scramble_word_with(part.to_owned(), scrambler, intensity, rng)
+        }
+    }
+
+    /// Shuffles the interior graphemes of a word according to the Scrambler's  
+    /// ShuffleMode. Readable and hard modes draw several candidate orderings and keep  
+    /// the one whose letter bigrams, anchors included, are the most (or least) plausible  
+    /// in the Scrambler's language, e.g. "sntdteus" over "sdtnteus" for "students".
+    ///
+    /// # Arguments
+    ///
+    /// - `middle` (`&mut [&str]`) - The interior graphemes
+    /// - `anchors` (`(&str, &str)`) - The graphemes right before and after the interior
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of the interior taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    fn shuffle_interior<R: Rng + ?Sized>(
+        middle: &mut [&str],
+        anchors: (&str, &str),
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) {
+        let mode: ShuffleMode = scrambler.shuffle_mode();
+        if mode == ShuffleMode::Uniform {
+            shuffle_positions(middle, intensity, rng);
+            return;
+        }
+
+        let table = scrambler.language().bigrams();
+        let original: Vec<&str> = middle.to_vec();
+        let mut best: Option<(Vec<&str>, (usize, f64))> = None;
+
+        for _ in 0..CANDIDATE_SHUFFLES {
+            let mut candidate: Vec<&str> = original.clone();
+            shuffle_positions(&mut candidate, intensity, rng);
+
+            let word: String = [&[anchors.0], &candidate[..], &[anchors.1]]
+                .concat()
+                .concat();
+            let (illegal, log_count) = table.plausibility(&word);
+            // lower scores win
+            let score: (usize, f64) = match mode {
+                ShuffleMode::Hard => (usize::MAX - illegal, log_count),
+                _ => (illegal, -log_count),
+            };
+
+            // an intact interior is only kept when nothing else came up
+            let intact: bool = candidate == original;
+            let better: bool = match &best {
+                None => true,
+                Some((b, _)) if intact != (*b == original) => !intact,
+                Some((_, s)) => score.0 < s.0 || (score.0 == s.0 && score.1 < s.1),
+            };
+            if better {
+                best = Some((candidate, score));
+            }
+        }
+
+        if let Some((candidate, _)) = best {
+            middle.copy_from_slice(&candidate);
+        }
+    }
+
+    /// Shuffles the interior graphemes uniformly. With an intensity below 1.0 only a  
+    /// random subset of the positions takes part in the shuffle, the rest stay put, e.g.  
+    /// intensity 0.5 on "bcdefg" shuffles three of the six letters among themselves
+    ///
+    /// # Arguments
+    ///
+    /// - `middle` (`&mut [T]`) - The interior graphemes, or jamo
+    /// - `intensity` (`f64`) - Fraction of the interior taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    fn shuffle_positions<T: Copy, R: Rng + ?Sized>(middle: &mut [T], intensity: f64, rng: &mut R) {
+        if intensity >= 1.0 {
+            middle.shuffle(rng);
+            return;
+        }
+
+        let count: usize = (intensity.max(0.0) * middle.len() as f64).round() as usize;
+        if count < 2 {
+            return;
+        }
+
+        let mut positions: Vec<usize> = sample(rng, middle.len(), count).into_vec();
+        positions.sort_unstable();
+
+        let mut picked: Vec<T> = positions.iter().map(|&i| middle[i]).collect();
+        picked.shuffle(rng);
+
+        for (position, grapheme) in positions.iter().zip(picked) {
+            middle[*position] = grapheme;
+        }
+    }
+
+    /// Checks whether the cluster, `len` graphemes long, sits at grapheme `index`,  
+    /// ignoring case and normalization form
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `offsets` (`&[usize]`) - Byte offset of each grapheme, plus the end of the word
+    /// - `index` (`usize`) - The grapheme index to test
+    /// - `len` (`usize`) - The cluster's length in graphemes
+    /// - `cluster` (`&str`) - The lowercase cluster
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not the cluster matches
+    ///
+    fn cluster_at(s: &str, offsets: &[usize], index: usize, len: usize, cluster: &str) -> bool {
+        index + len < offsets.len()
+            && s[offsets[index]..offsets[index + len]]
+                .to_lowercase()
+                .nfc()
+                .eq(cluster.nfc())
+    }
+
+    /// Determines if a grapheme must stay with the next one, e.g. Thai "เ", or with  
+    /// aksharas a Tamil consonant ending in a virama
+    ///
+    /// # Arguments
+    ///
+    /// - `grapheme` (`&str`) - The grapheme
+    /// - `aksharas` (`bool`) - Whether or not Indic words are cut into aksharas
+    ///
+    /// # Returns
+    ///
+    /// - `bool` - Whether or not the grapheme is bound to the next one
+    ///
+    fn is_bound(grapheme: &str, aksharas: bool) -> bool {
+        binds_forward(grapheme) || (aksharas && ends_with_virama(grapheme))
+    }
+
+    /// Grows the first and last anchors so they take in a cluster they are part of, e.g.  
+    /// "ship" keeps "sh" in front and "catch" keeps "ch" at the back. Graphemes bound to  
+    /// the next one, such as Thai "เ", always stay with it, so that with aksharas the  
+    /// first and last aksharas are kept whole.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `offsets` (`&[usize]`) - Byte offset of each grapheme, plus the end of the word
+    /// - `start_index` (`usize`) - Index of the first anchor grapheme
+    /// - `end_index` (`usize`) - Index of the last anchor grapheme
+    /// - `clusters` (`&[String]`) - Lowercase clusters, longest first
+    /// - `aksharas` (`bool`) - Whether or not Indic words are cut into aksharas
+    ///
+    /// # Returns
+    ///
+    /// - `(usize, usize)` - The indices of the anchors' innermost graphemes
+    ///
+    fn extend_anchors(
+        s: &str,
+        offsets: &[usize],
+        start_index: usize,
+        end_index: usize,
+        clusters: &[String],
+        aksharas: bool,
+    ) -> (usize, usize) {
+        let mut start: usize = start_index;
+        let mut end: usize = end_index;
+
+        for cluster in clusters {
+            let len: usize = cluster.graphemes(true).count();
+            if start_index + len <= end_index && cluster_at(s, offsets, start_index, len, cluster) {
+                start = start_index + len - 1;
+                break;
+            }
+        }
+
+        for cluster in clusters {
+            let len: usize = cluster.graphemes(true).count();
+            if end_index + 1 > start + len
+                && cluster_at(s, offsets, end_index + 1 - len, len, cluster)
+            {
+                end = end_index + 1 - len;
+                break;
+            }
+        }
+
+        let grapheme = |i: usize| &s[offsets[i]..offsets[i + 1]];
+        while start + 1 < end && is_bound(grapheme(start), aksharas) {
+            start += 1;
+        }
+        while end > start + 1 && is_bound(grapheme(end - 1), aksharas) {
+            end -= 1;
+        }
+
+        (start, end)
+    }
+
+    /// Grows the anchors so they take in a matching prefix and suffix, e.g. "unlikely"  
+    /// keeps "un" and "ly" so that only "like" is shuffled. An affix is skipped when  
+    /// fewer than two graphemes of stem would be left to shuffle.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `offsets` (`&[usize]`) - Byte offset of each grapheme, plus the end of the word
+    /// - `core` (`(usize, usize)`) - Indices of the word's first and last letters
+    /// - `anchors` (`(usize, usize)`) - The anchors' innermost graphemes so far
+    /// - `prefixes` (`&[String]`) - Lowercase prefixes, longest first
+    /// - `suffixes` (`&[String]`) - Lowercase suffixes, longest first
+    ///
+    /// # Returns
+    ///
+    /// - `(usize, usize)` - The indices of the anchors' innermost graphemes
+    ///
+    fn extend_affixes(
+        s: &str,
+        offsets: &[usize],
+        core: (usize, usize),
+        anchors: (usize, usize),
+        prefixes: &[String],
+        suffixes: &[String],
+    ) -> (usize, usize) {
+        let (mut start, mut end) = anchors;
+
+        for prefix in prefixes {
+            let len: usize = prefix.graphemes(true).count();
+            let inner: usize = core.0 + len - 1;
+            if inner > start && inner + 2 < end && cluster_at(s, offsets, core.0, len, prefix) {
+                start = inner;
+                break;
+            }
+        }
+
+        for suffix in suffixes {
+            let len: usize = suffix.graphemes(true).count();
+            let Some(inner) = (core.1 + 1).checked_sub(len) else {
+                continue;
+            };
+            if inner < end && inner > start + 2 && cluster_at(s, offsets, inner, len, suffix) {
+                end = inner;
+                break;
+            }
+        }
+
+        (start, end)
+    }
+
+    /// Cuts the interior into shuffle units, keeping each cluster together as a  
+    /// single unit, e.g. "tchin" with "ch" => ["t", "ch", "i", "n"], along with graphemes  
+    /// bound to the next one, e.g. Thai "เ" + "ร" or Tamil "க்" + "ஷே"
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `offsets` (`&[usize]`) - Byte offset of each grapheme, plus the end of the word
+    /// - `range` (`Range<usize>`) - The interior, as grapheme indices
+    /// - `clusters` (`&[String]`) - Lowercase clusters, longest first
+    /// - `aksharas` (`bool`) - Whether or not Indic words are cut into aksharas
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<&str>` - The units, in order
+    ///
+    fn group_clusters<'a>(
+        s: &'a str,
+        offsets: &[usize],
+        range: Range<usize>,
+        clusters: &[String],
+        aksharas: bool,
+    ) -> Vec<&'a str> {
+        let mut units: Vec<&str> = Vec::new();
+        let mut index: usize = range.start;
+
+        while index < range.end {
+            let mut len: usize = clusters
+                .iter()
+                .map(|c| c.graphemes(true).count())
+                .zip(clusters)
+                .find(|(len, c)| index + len <= range.end && cluster_at(s, offsets, index, *len, c))
+                .map_or(1, |(len, _)| len);
+            while index + len < range.end
+                && is_bound(&s[offsets[index + len - 1]..offsets[index + len]], aksharas)
+            {
+                len += 1;
+            }
+
+            units.push(&s[offsets[index]..offsets[index + len]]);
+            index += len;
+        }
+
+        units
+    }
+
+    /// Splits text into alternating runs of whitespace and non-whitespace so that  
+    /// joining the runs gives back the original text, e.g.  
+    /// "hi  there\n" => ["hi", "  ", "there", "\n"]
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The input text
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<&str>` - The runs, in order
+    ///
+    pub(crate) fn split_whitespace_runs(s: &str) -> Vec<&str> {
+        let mut runs: Vec<&str> = Vec::new();
+        let mut start: usize = 0;
+        let mut in_whitespace: Option<bool> = None;
+
+        for (index, c) in s.char_indices() {
+            let ws = c.is_whitespace();
+            if in_whitespace.is_some_and(|prev| prev != ws) {
+                runs.push(&s[start..index]);
+                start = index;
+            }
+            in_whitespace = Some(ws);
+        }
+
+        if start < s.len() {
+            runs.push(&s[start..]);
+        }
+
+        runs
+    }
+
     /// Checks if a string slice starts with a numeric character.  
     /// Strings starting with numeric characters should be kept as-is and not typoglycemified, e.g.  
-    /// date (12/22/1986) and/or time (15:32)
+    /// date (12/22/1986) and/or time (15:32), also when written with Arabic-Indic digits (١٥:٣٢)
     ///
     fn is_numeric_string(s: &str) -> bool {
         let atoi_str: Option<u64> = atoi::<u64>(s.as_bytes());
+        let arabic_indic: bool = s.chars().next().is_some_and(|c| {
+            ARABIC_INDIC_DIGITS.contains(&(c as usize))
+                || EXTENDED_ARABIC_INDIC_DIGITS.contains(&(c as usize))
+        });
 
-        atoi_str.is_some()
+        atoi_str.is_some() || arabic_indic
     }
 
     /// The primary typoglycemic function of this crate.  
@@ -227,10 +1030,33 @@ pub mod utils {
     /// let result = typoglycemia::utils::scramble_word(sentence);
     /// assert_eq!(result.len(), lng);
     pub fn scramble_word(s: String) -> String {
+        scramble_word_with(s, &Scrambler::default(), 1.0, &mut rng())
+    }
+
+    /// Same as scramble_word() but follows the Scrambler's configuration and draws its  
+    /// randomness from the supplied generator, which lets a seeded Scrambler reproduce its output.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`String`) - The word to scramble
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of interior characters taking part in the shuffle, 1.0 for all of them
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The scrambled word
+    ///
+    pub(crate) fn scramble_word_with<R: Rng + ?Sized>(
+        s: String,
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
         let input_as_str: &str = s.as_str();
 
         // vector of valid ASCII characters (usize)
-        let valid_chars: Vec<usize> = get_all_valid_ascii_chars();
+        let valid_chars: &HashSet<usize> = &VALID_CHARS;
 
         // get the graphemes
         let g: Vec<&str> = input_as_str.graphemes(true).collect::<Vec<&str>>();
@@ -240,37 +1066,158 @@ pub mod utils {
         //     return s;
         // }
 
+        let leading_digits: bool = scrambler.numeric_policy() == NumericPolicy::Leading;
+        if !leading_digits && is_numeric_token(input_as_str) {
+            return s;
+        }
+
+        if scrambler.hangul_mode() == HangulMode::Jamo
+            && !g.is_empty()
+            && !is_numeric_string(input_as_str)
+        {
+            let start_index = get_valid_start_index(input_as_str, valid_chars);
+            let end_index = get_valid_end_index(input_as_str, valid_chars);
+            if let Some(result) =
+                shuffle_jamo(input_as_str, start_index..end_index + 1, intensity, rng)
+            {
+                return result;
+            }
+        }
+
+        if !g.is_empty() && g.iter().any(|x| x.starts_with(is_cjk)) {
+            let start_index = get_valid_start_index(input_as_str, valid_chars);
+            let end_index = get_valid_end_index(input_as_str, valid_chars);
+            if let Some(result) = swap_cjk_pair(&g, start_index..end_index + 1, intensity, rng) {
+                return result;
+            }
+        }
+
+        if s.contains(MAQAF) {
+            return handle_maqaf_string(&s, scrambler, intensity, rng);
+        }
+
         if has_apostrophes(&s) && has_hyphens(&s) {
-            return handle_apostrophe_and_hyphenated_string(&s);
+            return handle_apostrophe_and_hyphenated_string(&s, scrambler, intensity, rng);
         }
 
         if has_apostrophes(&s) {
-            return handle_apostrophe_string(&s);
+            return handle_apostrophe_string(&s, scrambler, intensity, rng);
         }
 
         if has_hyphens(&s) {
-            return handle_hyphenated_string(&s);
+            return handle_hyphenated_string(&s, scrambler, intensity, rng);
+        }
+
+        if scrambler.subword_splitting() {
+            let parts: Vec<&str> = split_subwords(input_as_str);
+            if parts.len() > 1 {
+                return handle_subword_string(&parts, scrambler, intensity, rng);
+            }
+        }
+
+        if scrambler.numeric_policy() == NumericPolicy::ScrambleLetters
+            && input_as_str.contains(|c: char| c.is_numeric())
+            && input_as_str.contains(char::is_alphabetic)
+        {
+            return handle_alphanumeric_string(input_as_str, scrambler, intensity, rng);
         }
 
         // (grapheme length <= 3 or > 15) or numeric then return as-is
-        if g.len() <= 3 || g.len() > 15 || is_numeric_string(s.as_str()) {
+        if g.len() <= 3 || g.len() > 15 || (leading_digits && is_numeric_string(s.as_str())) {
             return s;
         }
 
-        let start_index = get_valid_start_index(input_as_str, &valid_chars);
-        let end_index = get_valid_end_index(input_as_str, &valid_chars);
+        let start_index = get_valid_start_index(input_as_str, valid_chars);
+        let end_index = get_valid_end_index(input_as_str, valid_chars);
 
         // for example, this weird string w/ only one valid ascii character -> __a__
         if start_index == end_index {
             return s;
         }
 
+        // byte offset of each grapheme, plus the end of the string
+        let offsets: Vec<usize> = input_as_str
+            .grapheme_indices(true)
+            .map(|(i, _)| i)
+            .chain([input_as_str.len()])
+            .collect();
+        let syllables: Vec<usize> = match scrambler.syllable_mode() {
+            SyllableMode::Off => Vec::new(),
+            _ => syllable_starts(
+                input_as_str,
+                &offsets,
+                start_index..end_index + 1,
+                scrambler,
+            ),
+        };
+
+        if scrambler.syllable_mode() == SyllableMode::Whole {
+            return shuffle_syllables(
+                input_as_str,
+                &offsets,
+                &syllables,
+                scrambler,
+                intensity,
+                rng,
+            );
+        }
+
+        let clusters: Vec<String> = scrambler.active_clusters();
+        let (prefixes, suffixes) = scrambler.active_affixes();
+        let aksharas: bool = scrambler.indic_mode() == IndicMode::Aksharas;
+        let anchors: (usize, usize) = extend_anchors(
+            input_as_str,
+            &offsets,
+            start_index,
+            end_index,
+            &clusters,
+            aksharas,
+        );
+        let (start_index, end_index) = extend_affixes(
+            input_as_str,
+            &offsets,
+            (start_index, end_index),
+            anchors,
+            &prefixes,
+            &suffixes,
+        );
+
         let first = &g[0..=start_index];
-        let middle = &g[start_index + 1..end_index];
         let last = &g[end_index..];
-
-        let mut mtv = middle.to_vec();
-        mtv.shuffle(&mut rng());
+        let pin_capitals: bool = scrambler.case_policy() == CasePolicy::Pinned;
+
+        // without syllables the interior is a single piece; pinned graphemes are pieces
+        // of their own so that they stay in place
+        let pinned = (start_index + 1..end_index)
+            .filter(|&i| is_pinned(g[i]) || (pin_capitals && g[i].starts_with(char::is_uppercase)))
+            .flat_map(|i| [i, i + 1]);
+        let mut bounds: Vec<usize> = [start_index + 1]
+            .into_iter()
+            .chain(syllables.into_iter().chain(pinned))
+            .filter(|&b| b > start_index && b < end_index)
+            .chain([end_index])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        let mut mtv: Vec<&str> = Vec::new();
+        for piece in bounds.windows(2) {
+            let mut units: Vec<&str> = group_clusters(
+                input_as_str,
+                &offsets,
+                piece[0]..piece[1],
+                &clusters,
+                aksharas,
+            );
+            shuffle_interior(
+                &mut units,
+                (g[piece[0] - 1], g[piece[1]]),
+                scrambler,
+                intensity,
+                rng,
+            );
+            mtv.extend(units);
+        }
         let middle_scrambled = &mtv[..];
 
         let concatenated = [first, middle_scrambled, last].concat();
@@ -278,12 +1225,199 @@ pub mod utils {
         concatenated.join("")
     }
 
+    /// Swaps two neighbouring characters of a Chinese or Japanese word of two or three  
+    /// characters, too short to have an interior, which gives the well-known effect  
+    /// of "研表究明，汉字序顺并不定一能影阅响读", e.g. "顺序" => "序顺". Longer words  
+    /// keep their first and last characters like any other word.
+    ///
+    /// # Arguments
+    ///
+    /// - `g` (`&[&str]`) - The graphemes of the word
+    /// - `core` (`Range<usize>`) - The word without its punctuation, as grapheme indices
+    /// - `intensity` (`f64`) - The chance that the pair is swapped
+    /// - `rng` (`&mut R`) - The random number generator driving the swap
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - The word, None unless the core is two or three CJK characters
+    ///
+    fn swap_cjk_pair<R: Rng + ?Sized>(
+        g: &[&str],
+        core: Range<usize>,
+        intensity: f64,
+        rng: &mut R,
+    ) -> Option<String> {
+        if !(2..=3).contains(&core.len()) || !g[core.clone()].iter().all(|x| x.starts_with(is_cjk))
+        {
+            return None;
+        }
+
+        let mut swapped: Vec<&str> = g.to_vec();
+        if rng.random_bool(intensity.clamp(0.0, 1.0)) {
+            let first: usize = core.start + rng.random_range(0..core.len() - 1);
+            swapped.swap(first, first + 1);
+        }
+
+        Some(swapped.concat())
+    }
+
+    /// Shuffles the jamo of a Korean word: initial consonants, vowels and final  
+    /// consonants each change places among their own position, so every block is  
+    /// recomposed into a valid syllable. The word's first jamo and its last one, the  
+    /// final consonant or else the vowel of the last block, stay put, e.g. "한국어" => "훈각어" or "학운거"
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `core` (`Range<usize>`) - The word without its punctuation, as grapheme indices
+    /// - `intensity` (`f64`) - Fraction of each position's jamo taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `Option<String>` - The scrambled word, None unless the core is all Hangul syllables
+    ///
+    fn shuffle_jamo<R: Rng + ?Sized>(
+        s: &str,
+        core: Range<usize>,
+        intensity: f64,
+        rng: &mut R,
+    ) -> Option<String> {
+        let g: Vec<&str> = s.graphemes(true).collect();
+        let mut blocks: Vec<Syllable> = Vec::new();
+        for grapheme in &g[core.clone()] {
+            let mut composed = grapheme.nfc();
+            match (
+                composed.next().and_then(Syllable::decompose),
+                composed.next(),
+            ) {
+                (Some(syllable), None) => blocks.push(syllable),
+                _ => return None,
+            }
+        }
+
+        let last: usize = blocks.len() - 1;
+        // blocks taking part for the initial consonant, the vowel and the final consonant
+        let slots: [Vec<usize>; 3] = [
+            (1..=last).collect(),
+            (0..=last)
+                .filter(|&i| i < last || blocks[last].tail != 0)
+                .collect(),
+            (0..last).filter(|&i| blocks[i].tail != 0).collect(),
+        ];
+        for (position, indices) in slots.into_iter().enumerate() {
+            let mut values: Vec<u32> = indices
+                .iter()
+                .map(|&i| *blocks[i].jamo_mut(position))
+                .collect();
+            shuffle_positions(&mut values, intensity, rng);
+            for (i, value) in indices.into_iter().zip(values) {
+                *blocks[i].jamo_mut(position) = value;
+            }
+        }
+
+        // blocks written as conjoining jamo are decomposed again
+        let scrambled = g[core.clone()].iter().zip(blocks).map(|(grapheme, block)| {
+            let composed: String = block.compose().to_string();
+            match grapheme.chars().count() {
+                1 => composed,
+                _ => composed.nfd().collect(),
+            }
+        });
+
+        Some(
+            g[..core.start]
+                .iter()
+                .map(|x| x.to_string())
+                .chain(scrambled)
+                .chain(g[core.end..].iter().map(|x| x.to_string()))
+                .collect(),
+        )
+    }
+
+    /// Finds where the syllables of a word start, using the Scrambler's hyphenator on  
+    /// the word stripped of its surrounding punctuation, e.g. "(hyphenation)" => [3, 7]
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `offsets` (`&[usize]`) - Byte offset of each grapheme, plus the end of the word
+    /// - `core` (`Range<usize>`) - The word without its punctuation, as grapheme indices
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    ///
+    /// # Returns
+    ///
+    /// - `Vec<usize>` - Grapheme index of each syllable but the first
+    ///
+    fn syllable_starts(
+        s: &str,
+        offsets: &[usize],
+        core: Range<usize>,
+        scrambler: &Scrambler,
+    ) -> Vec<usize> {
+        let base: usize = offsets[core.start];
+
+        scrambler
+            .hyphenator()
+            .breaks(&s[base..offsets[core.end]])
+            .into_iter()
+            .filter_map(|b| offsets.iter().position(|&o| o == base + b))
+            .collect()
+    }
+
+    /// Shuffles whole syllables, keeping the first and last syllables in place along  
+    /// with any surrounding punctuation, e.g. "un-be-liev-able" => "un-liev-be-able".  
+    /// Words with fewer than four syllables come back unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// - `s` (`&str`) - The word
+    /// - `offsets` (`&[usize]`) - Byte offset of each grapheme, plus the end of the word
+    /// - `starts` (`&[usize]`) - Grapheme index of each syllable but the first
+    /// - `scrambler` (`&Scrambler`) - The scrambler configuration
+    /// - `intensity` (`f64`) - Fraction of the inner syllables taking part in the shuffle
+    /// - `rng` (`&mut R`) - The random number generator driving the shuffle
+    ///
+    /// # Returns
+    ///
+    /// - `String` - The word with its inner syllables shuffled
+    ///
+    fn shuffle_syllables<R: Rng + ?Sized>(
+        s: &str,
+        offsets: &[usize],
+        starts: &[usize],
+        scrambler: &Scrambler,
+        intensity: f64,
+        rng: &mut R,
+    ) -> String {
+        let (Some(&first), Some(&last)) = (starts.first(), starts.last()) else {
+            return s.to_string();
+        };
+
+        let mut inner: Vec<&str> = starts
+            .windows(2)
+            .map(|w| &s[offsets[w[0]]..offsets[w[1]]])
+            .collect();
+        let anchors: (&str, &str) = (&s[..offsets[first]], &s[offsets[last]..]);
+        shuffle_interior(&mut inner, anchors, scrambler, intensity, rng);
+
+        [anchors.0, &inner.concat(), anchors.1].concat()
+    }
+
     // testing pub / private functions
     #[cfg(test)]
+    #[allow(
+        clippy::bool_assert_comparison,
+        clippy::explicit_auto_deref,
+        clippy::get_first,
+        clippy::get_last_with_len,
+        clippy::iter_nth_zero
+    )]
     mod tests {
 
         // Import all items from the parent module
         use super::*;
+        use crate::bigrams::BigramTable;
 
         #[test]
         fn test_is_numeric_string() {
@@ -300,6 +1434,111 @@ pub mod utils {
             }
         }
 
+        #[test]
+        fn test_numeric_policies() {
+            let mut rng = rng();
+            let leading = Scrambler::default();
+            let recognize = Scrambler::default().with_numeric_policy(NumericPolicy::Recognize);
+            let letters = Scrambler::default().with_numeric_policy(NumericPolicy::ScrambleLetters);
+
+            for _ in 0..50 {
+                for word in ["$100.00", "v1.2.3-beta", "0xDEADBEEF", "+1-555-123-4567"] {
+                    let result = scramble_word_with(word.to_string(), &recognize, 1.0, &mut rng);
+                    assert_eq!(result, word);
+                }
+                assert_eq!(
+                    scramble_word_with("4x4wheel".to_string(), &leading, 1.0, &mut rng),
+                    "4x4wheel"
+                );
+
+                let result =
+                    scramble_word_with("2nd-handed".to_string(), &recognize, 1.0, &mut rng);
+                assert!(
+                    result.starts_with("2nd-h") && result.ends_with('d'),
+                    "{result}"
+                );
+
+                // digits stay put, letters only trade places with letters
+                let result =
+                    scramble_word_with("Covid19-related".to_string(), &letters, 1.0, &mut rng);
+                assert_eq!(&result[5..8], "19-", "{result}");
+                let result = scramble_word_with("abc123xyz".to_string(), &letters, 1.0, &mut rng);
+                assert!(result.starts_with('a') && result.ends_with('z'), "{result}");
+                assert_eq!(&result[3..6], "123", "{result}");
+                let mut sorted: Vec<char> = result.chars().collect();
+                sorted.sort_unstable();
+                assert_eq!(sorted.into_iter().collect::<String>(), "123abcxyz");
+            }
+
+            let mut moved: bool = false;
+            for _ in 0..100 {
+                let result = scramble_word_with("abc123xyz".to_string(), &letters, 1.0, &mut rng);
+                moved |= &result[1..3] != "bc";
+            }
+            assert!(moved);
+        }
+
+        #[test]
+        fn test_case_policies() {
+            assert_eq!(restore_case("McDonald", "MDcnoald"), "MdCnoald");
+            assert_eq!(restore_case("LaTeX", "LeTaX"), "LeTaX");
+            assert_eq!(restore_case("LaTeX", "LTeaX"), "LtEaX");
+            assert_eq!(restore_case("Straße", "Sßarte"), "Sßarte");
+
+            for word in ["NASA", "U.S.", "(HTML5),", "FAQs", "GPU's"] {
+                assert!(is_acronym(word), "{word}");
+            }
+            for word in ["A", "Nasa", "iPhone", "42"] {
+                assert!(!is_acronym(word), "{word}");
+            }
+
+            let mut rng = rng();
+            let pinned = Scrambler::default().with_case_policy(CasePolicy::Pinned);
+            for _ in 0..50 {
+                for (word, capitals) in [("McDonald", [0, 2]), ("JavaScript", [0, 4])] {
+                    let result = scramble_word_with(word.to_string(), &pinned, 1.0, &mut rng);
+                    let upper: Vec<usize> = result
+                        .char_indices()
+                        .filter(|(_, c)| c.is_uppercase())
+                        .map(|(i, _)| i)
+                        .collect();
+                    assert_eq!(upper, capitals, "{result}");
+                }
+            }
+        }
+
+        #[test]
+        fn test_subwords() {
+            assert_eq!(split_subwords("getUserName"), ["get", "User", "Name"]);
+            assert_eq!(split_subwords("XMLHttpRequest"), ["XML", "Http", "Request"]);
+            assert_eq!(
+                split_subwords("max__retry_count"),
+                ["max", "__", "retry", "_", "count"]
+            );
+            assert_eq!(
+                split_subwords("#ThrowbackThursday"),
+                ["#Throwback", "Thursday"]
+            );
+            assert_eq!(split_subwords("user2Name"), ["user2", "Name"]);
+            assert_eq!(split_subwords("hello"), ["hello"]);
+
+            let scrambler = Scrambler::default().with_subword_splitting(true);
+            let mut rng = rng();
+            for _ in 0..50 {
+                for word in ["`getUserName`,", "max_retry_count", "#ThrowbackThursday"] {
+                    let result = scramble_word_with(word.to_string(), &scrambler, 1.0, &mut rng);
+                    let before: Vec<&str> = split_subwords(word);
+                    let after: Vec<&str> = split_subwords(&result);
+                    assert_eq!(before.len(), after.len(), "{result}");
+                    for (b, a) in before.iter().zip(after) {
+                        assert_eq!(b.len(), a.len(), "{result}");
+                        assert_eq!(b.chars().next(), a.chars().next(), "{result}");
+                        assert_eq!(b.chars().last(), a.chars().last(), "{result}");
+                    }
+                }
+            }
+        }
+
         #[test]
         fn test_get_valid_start_index() {
             let all_valid_ascii = get_all_valid_ascii_chars();
@@ -316,6 +1555,214 @@ pub mod utils {
             }
         }
 
+        #[test]
+        fn test_valid_index_ignores_normalization_form() {
+            let all_valid_ascii = get_all_valid_ascii_chars();
+
+            for word in ["\u{e9}col\u{e9}", "e\u{301}cole\u{301}"] {
+                assert_eq!(get_valid_start_index(word, &all_valid_ascii), 0);
+                assert_eq!(get_valid_end_index(word, &all_valid_ascii), 4);
+            }
+        }
+
+        #[test]
+        fn test_graphemes_travel_together() {
+            let mut r = rng();
+            for word in [
+                "re\u{301}sume\u{301}s",
+                "fam\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}ily",
+                "lo\u{2764}\u{fe0f}ving",
+            ] {
+                let mut expected: Vec<&str> = word.graphemes(true).collect();
+                expected.sort_unstable();
+
+                for _ in 0..20 {
+                    let result =
+                        scramble_word_with(word.to_string(), &Scrambler::new(), 1.0, &mut r);
+                    let mut graphemes: Vec<&str> = result.graphemes(true).collect();
+                    graphemes.sort_unstable();
+                    assert_eq!(graphemes, expected);
+                }
+            }
+        }
+
+        #[test]
+        fn test_valid_index_greek_and_cyrillic() {
+            let all_valid_ascii = get_all_valid_ascii_chars();
+
+            for word in ["«Привет»,", "(Καλημέρα)", "—ἄνθρωπος!"] {
+                assert_eq!(get_valid_start_index(word, &all_valid_ascii), 1);
+            }
+            assert_eq!(get_valid_end_index("«Привет»,", &all_valid_ascii), 6);
+            assert_eq!(get_valid_end_index("(Καλημέρα)", &all_valid_ascii), 8);
+        }
+
+        #[test]
+        fn test_valid_index_hebrew_and_arabic() {
+            let all_valid_ascii = get_all_valid_ascii_chars();
+
+            // logical order, whatever the display direction
+            assert_eq!(get_valid_start_index("(שָׁלוֹם),", &all_valid_ascii), 1);
+            assert_eq!(get_valid_end_index("(שָׁלוֹם),", &all_valid_ascii), 4);
+            assert_eq!(get_valid_start_index("«مرحبا»؟", &all_valid_ascii), 1);
+            assert_eq!(get_valid_end_index("«مرحبا»؟", &all_valid_ascii), 5);
+            assert_eq!(
+                get_valid_end_index("\u{2067}كتاب\u{2069}.", &all_valid_ascii),
+                4
+            );
+            assert!(is_numeric_string("١٩٨٦/١٢/٢٢"));
+        }
+
+        #[test]
+        fn test_right_to_left_words() {
+            let scrambler = Scrambler::default();
+            let mut rng = rng();
+
+            for _ in 0..20 {
+                // niqqud travels with its letter
+                let result = scramble_word_with("בְּרֵאשִׁית".to_string(), &scrambler, 1.0, &mut rng);
+                let mut letters: Vec<&str> = result.graphemes(true).collect();
+                assert_eq!(letters.first(), Some(&"בְּ"));
+                assert_eq!(letters.last(), Some(&"ת"));
+                letters.sort_unstable();
+                let mut expected: Vec<&str> = "בְּרֵאשִׁית".graphemes(true).collect();
+                expected.sort_unstable();
+                assert_eq!(letters, expected);
+
+                // bidi controls and gershayim stay in place, maqaf parts are scrambled alone
+                let result = scramble_word_with(
+                    "\u{202B}מדינה\u{200F}ישראל\u{202C}".to_string(),
+                    &scrambler,
+                    1.0,
+                    &mut rng,
+                );
+                let chars: Vec<char> = result.chars().collect();
+                assert_eq!(
+                    (chars[0], chars[6], chars[12]),
+                    ('\u{202B}', '\u{200F}', '\u{202C}')
+                );
+                let result = scramble_word_with("מנכ״לית".to_string(), &scrambler, 1.0, &mut rng);
+                assert_eq!(result.chars().nth(3), Some('״'));
+                let result = scramble_word_with("בית־הספר".to_string(), &scrambler, 1.0, &mut rng);
+                assert!(result.starts_with("בית־ה") && result.ends_with('ר'));
+            }
+        }
+
+        #[test]
+        fn test_hangul_modes() {
+            let blocks = Scrambler::default();
+            let jamo = Scrambler::default().with_hangul_mode(HangulMode::Jamo);
+            let mut rng = rng();
+
+            for _ in 0..20 {
+                let result =
+                    scramble_word_with("«대한민국에서»".to_string(), &blocks, 1.0, &mut rng);
+                assert!(
+                    result.starts_with("«대") && result.ends_with("서»"),
+                    "{result}"
+                );
+
+                // ㅎ and the last vowel ㅓ stay put, the block shapes are kept
+                let result = scramble_word_with("한국어,".to_string(), &jamo, 1.0, &mut rng);
+                assert!(result.ends_with(','), "{result}");
+                let letters: Vec<Syllable> =
+                    result.chars().filter_map(Syllable::decompose).collect();
+                assert_eq!(letters.len(), 3);
+                assert_eq!((letters[0].lead, letters[2].vowel), (18, 4));
+                assert_eq!(letters[2].tail, 0);
+                let mut leads: Vec<u32> = letters.iter().map(|b| b.lead).collect();
+                leads.sort_unstable();
+                assert_eq!(leads, vec![0, 11, 18]);
+
+                // conjoining jamo keep their form
+                let nfd: String = "한국어".nfd().collect();
+                let result = scramble_word_with(nfd.clone(), &jamo, 1.0, &mut rng);
+                assert_eq!(result.chars().count(), nfd.chars().count());
+            }
+        }
+
+        #[test]
+        fn test_cjk_words() {
+            let scrambler = Scrambler::default();
+            let mut rng = rng();
+
+            for _ in 0..20 {
+                let result = scramble_word_with("顺序，".to_string(), &scrambler, 1.0, &mut rng);
+                assert_eq!(result, "序顺，");
+                let result = scramble_word_with("研究生".to_string(), &scrambler, 1.0, &mut rng);
+                assert!(["究研生", "研生究"].contains(&result.as_str()), "{result}");
+                let result = scramble_word_with("顺序".to_string(), &scrambler, 0.0, &mut rng);
+                assert_eq!(result, "顺序");
+
+                let result =
+                    scramble_word_with("「コンピューター」".to_string(), &scrambler, 1.0, &mut rng);
+                assert!(
+                    result.starts_with("「コ") && result.ends_with("ー」"),
+                    "{result}"
+                );
+            }
+            assert_eq!(
+                scramble_word_with("的".to_string(), &scrambler, 1.0, &mut rng),
+                "的"
+            );
+        }
+
+        #[test]
+        fn test_bound_graphemes() {
+            let scrambler = Scrambler::default();
+            let mut rng = rng();
+
+            for _ in 0..50 {
+                // "เ" and "แ" stay in front of their consonant, Khmer coengs above theirs
+                for (word, bound) in [
+                    ("(เหนียวแน่น)", ["เห", "แน"]),
+                    ("ประเทศไทย", ["เท", "ไท"]),
+                    ("សាស្ត្រាចារ្យ", ["ស្ត្រ", "រ្យ"]),
+                ] {
+                    let result = scramble_word_with(word.to_string(), &scrambler, 1.0, &mut rng);
+                    assert!(bound.iter().all(|b| result.contains(b)), "{result}");
+                    let mut letters: Vec<char> = result.chars().collect();
+                    let mut expected: Vec<char> = word.chars().collect();
+                    letters.sort_unstable();
+                    expected.sort_unstable();
+                    assert_eq!(letters, expected);
+                }
+            }
+        }
+
+        #[test]
+        fn test_indic_aksharas() {
+            let scrambler = Scrambler::default().with_indic_mode(IndicMode::Aksharas);
+            let mut rng = rng();
+
+            for _ in 0..50 {
+                // Tamil conjuncts are split into graphemes but stay whole as aksharas
+                for (word, first, last, bound) in [
+                    ("க்ஷேத்ரபாலகம்", "க்ஷே", "ம்", ["த்ர", "பா"]),
+                    ("பிரஸ்தாபித்தார்", "பி", "ர்", ["ஸ்தா", "த்தா"]),
+                    ("विद्यार्थियों", "वि", "यों", ["द्या", "र्थि"]),
+                ] {
+                    let result = scramble_word_with(word.to_string(), &scrambler, 1.0, &mut rng);
+                    assert!(result.starts_with(first), "{result}");
+                    assert!(result.ends_with(last), "{result}");
+                    assert!(bound.iter().all(|b| result.contains(b)), "{result}");
+                    let mut letters: Vec<char> = result.chars().collect();
+                    let mut expected: Vec<char> = word.chars().collect();
+                    letters.sort_unstable();
+                    expected.sort_unstable();
+                    assert_eq!(letters, expected);
+                }
+            }
+
+            let default = Scrambler::default();
+            let mut split: bool = false;
+            for _ in 0..200 {
+                let result = scramble_word_with("க்ஷேத்ரபாலகம்".to_string(), &default, 1.0, &mut rng);
+                split |= !result.starts_with("க்ஷே");
+            }
+            assert!(split);
+        }
+
         #[test]
         fn test_get_valid_end_index() {
             let all_valid_ascii = get_all_valid_ascii_chars();
@@ -350,7 +1797,8 @@ pub mod utils {
         #[test]
         fn test_single_apostrophe_string() {
             let s: &'static str = "O'Shaghennessy"; // Mr. Garvey
-            let result: String = handle_apostrophe_string(s);
+            let result: String =
+                handle_apostrophe_string(s, &Scrambler::default(), 1.0, &mut rng());
             let parts: Vec<&str> = result.split("'").collect();
 
             let first_word: &&str = parts.get(0).unwrap();
@@ -374,7 +1822,8 @@ pub mod utils {
         #[test]
         fn test_double_apostrophe_string() {
             let s: &'static str = "woulda'coulda'shoulda";
-            let result: String = handle_apostrophe_string(s);
+            let result: String =
+                handle_apostrophe_string(s, &Scrambler::default(), 1.0, &mut rng());
             let parts: Vec<&str> = result.split("'").collect();
 
             let first_word: &&str = parts.get(0).unwrap();
@@ -428,7 +1877,8 @@ pub mod utils {
         #[test]
         fn test_single_hyphen_string() {
             let s: &'static str = "nitty-gritty";
-            let result: String = handle_hyphenated_string(s);
+            let result: String =
+                handle_hyphenated_string(s, &Scrambler::default(), 1.0, &mut rng());
             let parts: Vec<&str> = result.split("-").collect();
 
             let first_word: &&str = parts.get(0).unwrap();
@@ -457,7 +1907,8 @@ pub mod utils {
         #[test]
         fn test_double_hyphen_string() {
             let s: &'static str = "over-the-counter";
-            let result: String = handle_hyphenated_string(s);
+            let result: String =
+                handle_hyphenated_string(s, &Scrambler::default(), 1.0, &mut rng());
             let parts: Vec<&str> = result.split("-").collect();
 
             let first_word: &&str = parts.get(0).unwrap();
@@ -494,7 +1945,8 @@ pub mod utils {
         #[test]
         fn test_triple_hyphen_string() {
             let s: &'static str = "head-in-the-clouds";
-            let result: String = handle_hyphenated_string(s);
+            let result: String =
+                handle_hyphenated_string(s, &Scrambler::default(), 1.0, &mut rng());
             let parts: Vec<&str> = result.split("-").collect();
 
             assert_eq!(parts.get(1), Some("in").as_ref());
@@ -547,6 +1999,201 @@ pub mod utils {
             }
         }
 
+        #[test]
+        fn test_shuffle_positions_intensity() {
+            let original: Vec<&str> = "bcdefghijk".graphemes(true).collect();
+
+            let mut untouched = original.clone();
+            shuffle_positions(&mut untouched, 0.0, &mut rng());
+            assert_eq!(untouched, original);
+
+            // at 30% at most three positions can move
+            for _ in 0..50 {
+                let mut partial = original.clone();
+                shuffle_positions(&mut partial, 0.3, &mut rng());
+                let moved = partial
+                    .iter()
+                    .zip(&original)
+                    .filter(|(a, b)| a != b)
+                    .count();
+                assert!(moved <= 3);
+
+                let mut sorted = partial.clone();
+                sorted.sort_unstable();
+                assert_eq!(sorted, original);
+            }
+        }
+
+        #[test]
+        fn test_readable_and_hard_modes() {
+            let table = BigramTable::english();
+            let readable = Scrambler::new().with_shuffle_mode(ShuffleMode::Readable);
+            let hard = Scrambler::new().with_shuffle_mode(ShuffleMode::Hard);
+
+            let mut readable_illegal: usize = 0;
+            let mut hard_illegal: usize = 0;
+            for _ in 0..20 {
+                let r =
+                    scramble_word_with("straightforward".to_string(), &readable, 1.0, &mut rng());
+                let h = scramble_word_with("straightforward".to_string(), &hard, 1.0, &mut rng());
+                assert_ne!(r, "straightforward");
+                assert!(r.starts_with('s') && r.ends_with('d'));
+                readable_illegal += table.plausibility(&r).0;
+                hard_illegal += table.plausibility(&h).0;
+            }
+
+            assert!(readable_illegal < hard_illegal);
+        }
+
+        #[test]
+        fn test_clusters() {
+            let clusters: Vec<String> = ["sch", "ch", "th", "ll"].map(String::from).to_vec();
+            let offsets = |s: &str| -> Vec<usize> {
+                s.grapheme_indices(true)
+                    .map(|(i, _)| i)
+                    .chain([s.len()])
+                    .collect()
+            };
+
+            let word = "Schlechtes";
+            let o = offsets(word);
+            assert_eq!(extend_anchors(word, &o, 0, 9, &clusters, false), (2, 9));
+            assert_eq!(
+                group_clusters(word, &o, 3..9, &clusters, false),
+                vec!["l", "e", "ch", "t", "e"]
+            );
+
+            let word = "Gallath";
+            let o = offsets(word);
+            assert_eq!(extend_anchors(word, &o, 0, 6, &clusters, false), (0, 5));
+            assert_eq!(
+                group_clusters(word, &o, 1..5, &clusters, false),
+                vec!["a", "ll", "a"]
+            );
+
+            // the anchors never overlap
+            let word = "sch";
+            let o = offsets(word);
+            assert_eq!(extend_anchors(word, &o, 0, 2, &clusters, false), (0, 1));
+        }
+
+        #[test]
+        fn test_syllable_modes() {
+            let within = Scrambler::new().with_syllable_mode(SyllableMode::Within);
+            let whole = Scrambler::new().with_syllable_mode(SyllableMode::Whole);
+            let mut r = rng();
+
+            for _ in 0..20 {
+                // hy-phen-ation: letters stay inside their syllable
+                let result = scramble_word_with("hyphenation,".to_string(), &within, 1.0, &mut r);
+                assert_eq!(&result[..2], "hy");
+                assert!(result.ends_with("n,"));
+                let mut phen: Vec<char> = result[2..6].chars().collect();
+                phen.sort_unstable();
+                assert_eq!(phen, vec!['e', 'h', 'n', 'p']);
+                let mut ation: Vec<char> = result[6..10].chars().collect();
+                ation.sort_unstable();
+                assert_eq!(ation, vec!['a', 'i', 'o', 't']);
+
+                // ex-per-i-men-tal: the inner syllables move as a whole
+                let result = scramble_word_with("experimental".to_string(), &whole, 1.0, &mut r);
+                assert!(
+                    result.starts_with("ex") && result.ends_with("tal"),
+                    "{result}"
+                );
+                assert!(result.contains("per") && result.contains("men"), "{result}");
+            }
+
+            // a single inner syllable has nothing to trade places with
+            assert_eq!(
+                scramble_word_with("hyphenation".to_string(), &whole, 1.0, &mut r),
+                "hyphenation"
+            );
+        }
+
+        #[test]
+        fn test_affixes() {
+            let prefixes: Vec<String> = ["under", "un", "re"].map(String::from).to_vec();
+            let suffixes: Vec<String> = ["ing", "ly"].map(String::from).to_vec();
+            let offsets = |s: &str| -> Vec<usize> {
+                s.grapheme_indices(true)
+                    .map(|(i, _)| i)
+                    .chain([s.len()])
+                    .collect()
+            };
+
+            // "un" + "like" + "ly"
+            let word = "Unlikely";
+            let o = offsets(word);
+            assert_eq!(
+                extend_affixes(word, &o, (0, 7), (0, 7), &prefixes, &suffixes),
+                (1, 6)
+            );
+
+            // "r" + "unn" + "ing", the punctuation stays outside
+            let word = "(running)";
+            let o = offsets(word);
+            assert_eq!(
+                extend_affixes(word, &o, (1, 7), (1, 7), &prefixes, &suffixes),
+                (1, 5)
+            );
+
+            // "ring" would leave no stem to shuffle
+            let word = "ring";
+            let o = offsets(word);
+            assert_eq!(
+                extend_affixes(word, &o, (0, 3), (0, 3), &prefixes, &suffixes),
+                (0, 3)
+            );
+
+            let scrambler = Scrambler::new().with_affix_preservation(true);
+            let mut r = rng();
+            for _ in 0..20 {
+                let result = scramble_word_with("unbreakable".to_string(), &scrambler, 1.0, &mut r);
+                assert!(
+                    result.starts_with("un") && result.ends_with("able"),
+                    "{result}"
+                );
+            }
+        }
+
+        #[test]
+        fn test_elisions_and_clitics() {
+            let french = Scrambler::new().with_language(crate::Language::French);
+            let italian = Scrambler::new().with_language(crate::Language::Italian);
+            let mut r = rng();
+
+            for _ in 0..20 {
+                let result = scramble_word_with("jusqu'ici".to_string(), &french, 1.0, &mut r);
+                assert_eq!(result, "jusqu'ici");
+
+                let result = scramble_word_with("disent-elles".to_string(), &french, 1.0, &mut r);
+                assert!(result.ends_with("-elles"), "{result}");
+
+                let result = scramble_word_with("quell'anno".to_string(), &italian, 1.0, &mut r);
+                assert!(result.starts_with("quell'"), "{result}");
+            }
+
+            // the English profile has no such elision
+            let english = Scrambler::new();
+            let results: Vec<String> = (0..20)
+                .map(|_| scramble_word_with("quell'anno".to_string(), &english, 1.0, &mut r))
+                .collect();
+            assert!(results.iter().any(|r| !r.starts_with("quell'")));
+        }
+
+        #[test]
+        fn test_split_whitespace_runs() {
+            assert_eq!(
+                split_whitespace_runs(" hi  there\n\nyou"),
+                vec![" ", "hi", "  ", "there", "\n\n", "you"]
+            );
+            assert!(split_whitespace_runs("").is_empty());
+
+            let text = "Once upon\ta  midnight\n";
+            assert_eq!(split_whitespace_runs(text).concat(), text);
+        }
+
         #[test]
         fn test_one_valid_ascii_char() {
             let mut map: std::collections::HashMap<String, String> =
@@ -0,0 +1,716 @@
+use std::ops::Range;
+
+use crate::markup::{Tag, Unit, collect_units, decode_numeric, read_tag, scramble_runs};
+
+/// Elements whose text is left alone by default: DocBook and DITA code, commands,
+/// file names and other literals
+const SKIPPED: &[&str] = &[
+    "apiname",
+    "classname",
+    "cmdname",
+    "code",
+    "codeblock",
+    "codeph",
+    "command",
+    "computeroutput",
+    "email",
+    "filename",
+    "filepath",
+    "function",
+    "literal",
+    "literallayout",
+    "msgblock",
+    "msgph",
+    "option",
+    "parameter",
+    "parmname",
+    "programlisting",
+    "replaceable",
+    "screen",
+    "systemitem",
+    "systemoutput",
+    "uri",
+    "userinput",
+    "varname",
+];
+
+/// Inline elements by default, which a word may run through, e.g.
+/// "w<emphasis>or</emphasis>d": DocBook and DITA phrase-level markup
+const INLINE: &[&str] = &[
+    "abbrev",
+    "acronym",
+    "b",
+    "cite",
+    "citetitle",
+    "emphasis",
+    "firstterm",
+    "foreignphrase",
+    "glossterm",
+    "i",
+    "keyword",
+    "link",
+    "olink",
+    "ph",
+    "phrase",
+    "q",
+    "quote",
+    "sub",
+    "subscript",
+    "sup",
+    "superscript",
+    "term",
+    "tm",
+    "trademark",
+    "u",
+    "wordasword",
+];
+
+/// How a step relates to the step before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    /// "/", a child, or the root element for the first step
+    Child,
+    /// "//", a descendant at any depth
+    Descendant,
+}
+
+/// One step of an XmlPath, e.g. "//section[@role='intro']"
+#[derive(Debug, Clone)]
+struct Step {
+    axis: Axis,
+    /// Element name or "*"
+    name: String,
+    /// Attribute the element must have, and the value it must have if any
+    predicate: Option<(String, Option<String>)>,
+}
+
+/// An element on the path from the root to the current token
+struct Element {
+    name: String,
+    /// Attribute names and their values, as written
+    attributes: Vec<(String, String)>,
+}
+
+/// Determines if a name test matches an element or attribute name. Names are matched
+/// as written, a test without a prefix matches the local name whatever the prefix,
+/// e.g. "title" matches "db:title" but "dita:title" does not match "db:title".
+///
+/// # Arguments
+///
+/// - `test` (`&str`) - The name test, e.g. "*", "title" or "db:title"
+/// - `name` (`&str`) - The qualified name
+///
+/// # Returns
+///
+/// - `bool` - Whether or not the name matches
+///
+fn name_matches(test: &str, name: &str) -> bool {
+    test == "*" || test == name || (!test.contains(':') && name.rsplit(':').next() == Some(test))
+}
+
+/// Determines if a string is a name test: "*" or an XML name
+///
+/// # Arguments
+///
+/// - `name` (`&str`) - The candidate
+///
+/// # Returns
+///
+/// - `bool` - Whether or not it can be used as a name test
+///
+fn is_name(name: &str) -> bool {
+    name == "*"
+        || name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_-.:".contains(c))
+}
+
+impl Step {
+    /// Parses a step, e.g. "para", "*", "section[@role]" or "section[@role='intro']"
+    fn parse(axis: Axis, step: &str) -> Option<Step> {
+        let (name, predicate) = match step.split_once('[') {
+            Some((name, predicate)) => (name, Some(predicate.strip_suffix(']')?)),
+            None => (step, None),
+        };
+        if !is_name(name) {
+            return None;
+        }
+
+        let predicate: Option<(String, Option<String>)> = match predicate {
+            Some(predicate) => {
+                let predicate: &str = predicate.trim().strip_prefix('@')?;
+                let (attribute, value) = match predicate.split_once('=') {
+                    Some((attribute, value)) => {
+                        let value: &str = value.trim();
+                        let unquoted: &str = value
+                            .strip_prefix('\'')
+                            .and_then(|v| v.strip_suffix('\''))
+                            .or_else(|| value.strip_prefix('"')?.strip_suffix('"'))?;
+                        (attribute.trim(), Some(unquoted.to_string()))
+                    }
+                    None => (predicate.trim(), None),
+                };
+                if !is_name(attribute) {
+                    return None;
+                }
+                Some((attribute.to_string(), value))
+            }
+            None => None,
+        };
+
+        Some(Step {
+            axis,
+            name: name.to_string(),
+            predicate,
+        })
+    }
+
+    /// Determines if the step matches an element
+    fn matches(&self, element: &Element) -> bool {
+        name_matches(&self.name, &element.name)
+            && self.predicate.as_ref().is_none_or(|(attribute, value)| {
+                element.attributes.iter().any(|(a, v)| {
+                    name_matches(attribute, a) && value.as_ref().is_none_or(|value| value == v)
+                })
+            })
+    }
+}
+
+/// XmlPath selects elements, or attributes of elements, with a small subset of XPath:
+/// "/" for a child, "//" for a descendant at any depth, "*" for any element, an
+/// "[@attribute]" or "[@attribute='value']" predicate per step and a final "@attribute"
+/// step to select attribute values. A path that does not start with "/" matches at any
+/// depth. Names are matched as written, a name without a prefix matches any prefix.
+/// Predicate values are compared with the attribute value as written, e.g.
+///
+/// ```
+/// use typoglycemia::XmlPath;
+///
+/// assert!(XmlPath::parse("/book/chapter/para").is_some());
+/// assert!(XmlPath::parse("//section[@role='intro']//para").is_some());
+/// assert!(XmlPath::parse("figure/@alt").is_some());
+/// assert!(XmlPath::parse("//para[@role").is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct XmlPath {
+    steps: Vec<Step>,
+    attribute: Option<String>,
+}
+
+impl XmlPath {
+    /// Parses a path
+    ///
+    /// # Arguments
+    ///
+    /// - `path` (`&str`) - The path, e.g. "//chapter/title", "//p[@audience='novice']" or "//image/@alt"
+    ///
+    /// # Returns
+    ///
+    /// - `Option<XmlPath>` - The path, None if it is malformed
+    ///
+    pub fn parse(path: &str) -> Option<XmlPath> {
+        let mut rest: &str = path.trim();
+        let mut axis: Axis = Axis::Descendant;
+        if let Some(r) = rest.strip_prefix("//") {
+            rest = r;
+        } else if let Some(r) = rest.strip_prefix('/') {
+            rest = r;
+            axis = Axis::Child;
+        }
+
+        let mut steps: Vec<Step> = Vec::new();
+        loop {
+            // the next "/" outside of a predicate ends the step
+            let mut bracket: bool = false;
+            let mut quote: Option<char> = None;
+            let end: usize = rest
+                .char_indices()
+                .find(|&(_, c)| {
+                    match (quote, c) {
+                        (Some(q), _) if q == c => quote = None,
+                        (Some(_), _) => {}
+                        (None, '\'' | '"') => quote = Some(c),
+                        (None, '[') => bracket = true,
+                        (None, ']') => bracket = false,
+                        (None, '/') => return !bracket,
+                        _ => {}
+                    }
+                    false
+                })
+                .map_or(rest.len(), |(i, _)| i);
+
+            let step: &str = &rest[..end];
+            if let Some(attribute) = step.strip_prefix('@') {
+                if end != rest.len() || !is_name(attribute) {
+                    return None;
+                }
+                return Some(XmlPath {
+                    steps,
+                    attribute: Some(attribute.to_string()),
+                });
+            }
+            steps.push(Step::parse(axis, step)?);
+            if end == rest.len() {
+                return Some(XmlPath {
+                    steps,
+                    attribute: None,
+                });
+            }
+
+            rest = &rest[end..];
+            (rest, axis) = match rest.strip_prefix("//") {
+                Some(r) => (r, Axis::Descendant),
+                None => (&rest[1..], Axis::Child),
+            };
+        }
+    }
+
+    /// Determines if the steps match the innermost element of a stack
+    fn matches(steps: &[Step], stack: &[Element]) -> bool {
+        let (Some(step), Some(element)) = (steps.last(), stack.last()) else {
+            return false;
+        };
+        if !step.matches(element) {
+            return false;
+        }
+
+        let steps: &[Step] = &steps[..steps.len() - 1];
+        let ancestors: &[Element] = &stack[..stack.len() - 1];
+        match step.axis {
+            Axis::Child if steps.is_empty() => ancestors.is_empty(),
+            Axis::Child => Self::matches(steps, ancestors),
+            Axis::Descendant if steps.is_empty() => true,
+            Axis::Descendant => {
+                (1..=ancestors.len()).any(|k| Self::matches(steps, &ancestors[..k]))
+            }
+        }
+    }
+
+    /// Determines if the path selects the text of the innermost element of a stack,
+    /// which it does when it selects the element or one of its ancestors
+    fn selects_text(&self, stack: &[Element]) -> bool {
+        self.attribute.is_none()
+            && (1..=stack.len()).any(|k| Self::matches(&self.steps, &stack[..k]))
+    }
+
+    /// Determines if the path selects an attribute of the innermost element of a stack
+    fn selects_attribute(&self, stack: &[Element], attribute: &str) -> bool {
+        self.attribute
+            .as_ref()
+            .is_some_and(|a| name_matches(a, attribute))
+            && (self.steps.is_empty() || Self::matches(&self.steps, stack))
+    }
+}
+
+/// XmlOptions chooses what typoglycemia_xml_with() scrambles, e.g.
+///
+/// ```
+/// use typoglycemia::{XmlOptions, XmlPath};
+///
+/// let options = XmlOptions::new()
+///     .with_paths([
+///         XmlPath::parse("//chapter//para").unwrap(),
+///         XmlPath::parse("//imagedata/@alt").unwrap(),
+///     ])
+///     .with_skipped_elements(["programlisting", "literal"])
+///     .with_inline_elements(["emphasis", "link"]);
+///
+/// assert_eq!(options.paths().len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct XmlOptions {
+    paths: Vec<XmlPath>,
+    skipped: Vec<String>,
+    inline: Vec<String>,
+}
+
+impl Default for XmlOptions {
+    fn default() -> Self {
+        XmlOptions {
+            paths: Vec::new(),
+            skipped: SKIPPED.iter().map(|e| e.to_string()).collect(),
+            inline: INLINE.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+impl XmlOptions {
+    /// Returns options that scramble the text of every element but DocBook and DITA
+    /// code and literals, and no attribute values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scrambles only the text of the elements selected by the paths, and the values
+    /// of the attributes selected by paths ending in "@attribute"
+    ///
+    /// # Arguments
+    ///
+    /// - `paths` (`I`) - The paths, e.g. [XmlPath::parse("//para").unwrap()]
+    ///
+    pub fn with_paths<I: IntoIterator<Item = XmlPath>>(mut self, paths: I) -> Self {
+        self.paths = paths.into_iter().collect();
+        self
+    }
+
+    /// Replaces the elements whose text is left alone, even inside selected elements
+    ///
+    /// # Arguments
+    ///
+    /// - `elements` (`I`) - The element names, e.g. ["programlisting", "codeph"]
+    ///
+    pub fn with_skipped_elements<I, S>(mut self, elements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.skipped = elements
+            .into_iter()
+            .map(|e| e.as_ref().to_string())
+            .collect();
+        self
+    }
+
+    /// Replaces the inline elements, whose tags do not end a word. The tags of any
+    /// other element do.
+    ///
+    /// # Arguments
+    ///
+    /// - `elements` (`I`) - The element names, e.g. ["emphasis", "ph"]
+    ///
+    pub fn with_inline_elements<I, S>(mut self, elements: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.inline = elements
+            .into_iter()
+            .map(|e| e.as_ref().to_string())
+            .collect();
+        self
+    }
+
+    /// The paths selecting what is scrambled, all text when empty
+    pub fn paths(&self) -> &[XmlPath] {
+        &self.paths
+    }
+
+    /// The elements whose text is left alone
+    pub fn skipped_elements(&self) -> &[String] {
+        &self.skipped
+    }
+
+    /// The elements whose tags do not end a word
+    pub fn inline_elements(&self) -> &[String] {
+        &self.inline
+    }
+
+    /// Determines if the text of the innermost element of a stack is scrambled
+    fn selects_text(&self, stack: &[Element]) -> bool {
+        !stack
+            .iter()
+            .any(|e| self.skipped.iter().any(|s| name_matches(s, &e.name)))
+            && (self.paths.is_empty() || self.paths.iter().any(|p| p.selects_text(stack)))
+    }
+}
+
+/// A piece of an XML document
+enum Token {
+    /// Character data
+    Text(Range<usize>),
+    /// A start or end tag
+    Tag(Tag),
+    /// A comment, CDATA section, processing instruction or document type declaration
+    Markup,
+}
+
+/// Splits an XML document into text, tags and other markup
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+///
+/// # Returns
+///
+/// - `Vec<Token>` - The tokens, in order
+///
+fn tokenize(s: &str) -> Vec<Token> {
+    let bytes: &[u8] = s.as_bytes();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut text_start: usize = 0;
+    let mut i: usize = 0;
+
+    while i < bytes.len() {
+        if bytes[i] != b'<' {
+            i += 1;
+            continue;
+        }
+
+        let rest: &str = &s[i..];
+        let closing = |marker: &str, from: usize| -> usize {
+            s[from..]
+                .find(marker)
+                .map_or(s.len(), |p| from + p + marker.len())
+        };
+        let is_name_start =
+            |c: &u8| c.is_ascii_alphabetic() || *c == b'_' || *c == b':' || *c >= 0x80;
+        let next: Option<u8> = bytes.get(i + 1).copied();
+        let after: usize;
+        let mut token: Token = Token::Markup;
+        if rest.starts_with("<!--") {
+            after = closing("-->", i + 4);
+        } else if rest.starts_with("<![CDATA[") {
+            after = closing("]]>", i + 9);
+        } else if rest.starts_with("<?") {
+            after = closing("?>", i + 2);
+        } else if rest.starts_with("<!") {
+            // a document type declaration may hold an internal subset in brackets
+            let mut depth: usize = 0;
+            let mut j: usize = i + 2;
+            while j < bytes.len() && (bytes[j] != b'>' || depth > 0) {
+                match bytes[j] {
+                    b'[' => depth += 1,
+                    b']' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+                j += 1;
+            }
+            after = (j + 1).min(s.len());
+        } else if next.as_ref().is_some_and(is_name_start)
+            || (next == Some(b'/') && bytes.get(i + 2).is_some_and(is_name_start))
+        {
+            let (tag, end) = read_tag(s, i);
+            after = end;
+            token = Token::Tag(tag);
+        } else {
+            i += 1;
+            continue;
+        }
+
+        if text_start < i {
+            tokens.push(Token::Text(text_start..i));
+        }
+        tokens.push(token);
+        i = after;
+        text_start = after;
+    }
+
+    if text_start < s.len() {
+        tokens.push(Token::Text(text_start..s.len()));
+    }
+
+    tokens
+}
+
+/// Decodes a character reference the way XML does: numeric references and the five
+/// predefined entities only, e.g. "&#233;" => 'é', while "&eacute;" is left to a DTD
+///
+/// # Arguments
+///
+/// - `reference` (`&str`) - The reference, "&" and ";" included
+///
+/// # Returns
+///
+/// - `Option<char>` - The character, None for references XML does not predefine
+///
+fn decode(reference: &str) -> Option<char> {
+    let name: &str = reference.strip_prefix('&')?.strip_suffix(';')?;
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => decode_numeric(name),
+    }
+}
+
+/// Scrambles the text of an XML document, or of the elements and attributes selected
+/// by the options' paths, leaving every other byte as it was: tags, namespace
+/// declarations, CDATA sections, comments and processing instructions. Character
+/// references move along with the characters they stand for, and text nodes separated
+/// only by inline tags are scrambled together, e.g. "w<emphasis>or</emphasis>d".
+///
+/// # Arguments
+///
+/// - `s` (`&str`) - The document
+/// - `options` (`&XmlOptions`) - What to scramble
+/// - `scramble` (`F`) - Scrambles plain text
+///
+/// # Returns
+///
+/// - `String` - The document with its selected text scrambled
+///
+pub(crate) fn scramble_xml<F: FnMut(&str) -> String>(
+    s: &str,
+    options: &XmlOptions,
+    scramble: F,
+) -> String {
+    let mut runs: Vec<Vec<Unit>> = Vec::new();
+    let mut run: Vec<Unit> = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+
+    for token in tokenize(s) {
+        match token {
+            Token::Text(node) if options.selects_text(&stack) => {
+                collect_units(s, node, decode, &mut run, &mut runs)
+            }
+            Token::Text(_) | Token::Markup => runs.push(std::mem::take(&mut run)),
+            Token::Tag(tag) => {
+                if !options.inline.iter().any(|n| name_matches(n, &tag.name)) {
+                    runs.push(std::mem::take(&mut run));
+                }
+                if tag.end {
+                    if let Some(p) = stack.iter().rposition(|e| e.name == tag.name) {
+                        stack.truncate(p);
+                    }
+                    continue;
+                }
+
+                stack.push(Element {
+                    name: tag.name,
+                    attributes: tag
+                        .attributes
+                        .iter()
+                        .map(|(a, v)| (a.clone(), s[v.clone()].to_string()))
+                        .collect(),
+                });
+                let skipped: bool = stack
+                    .iter()
+                    .any(|e| options.skipped.iter().any(|n| name_matches(n, &e.name)));
+                for (attribute, value) in &tag.attributes {
+                    // namespace declarations are never scrambled
+                    if skipped || attribute == "xmlns" || attribute.starts_with("xmlns:") {
+                        continue;
+                    }
+                    if options
+                        .paths
+                        .iter()
+                        .any(|p| p.selects_attribute(&stack, attribute))
+                    {
+                        let mut units: Vec<Unit> = Vec::new();
+                        collect_units(s, value.clone(), decode, &mut units, &mut runs);
+                        runs.push(units);
+                    }
+                }
+                if tag.self_closing {
+                    stack.pop();
+                }
+            }
+        }
+    }
+    runs.push(run);
+
+    scramble_runs(s, &runs, scramble)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_segmentation::UnicodeSegmentation;
+
+    fn reverse(t: &str) -> String {
+        t.split(' ')
+            .map(|w| w.graphemes(true).rev().collect::<String>())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_xml_path() {
+        let element = |name: &str, attributes: &[(&str, &str)]| Element {
+            name: name.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(a, v)| (a.to_string(), v.to_string()))
+                .collect(),
+        };
+        let stack = [
+            element("db:book", &[]),
+            element("chapter", &[("role", "intro")]),
+            element("section", &[]),
+            element("para", &[("xml:lang", "en")]),
+        ];
+
+        for path in [
+            "para",
+            "//para",
+            "/book/chapter/section/para",
+            "/db:book//para",
+            "chapter[@role='intro']//para",
+            "*/para[@lang]",
+            "/book",
+        ] {
+            let parsed = XmlPath::parse(path).unwrap();
+            assert!(parsed.selects_text(&stack), "{path}");
+        }
+        for path in [
+            "/para",
+            "/dita:book",
+            "chapter/para",
+            "chapter[@role=\"outro\"]",
+        ] {
+            let parsed = XmlPath::parse(path).unwrap();
+            assert!(!parsed.selects_text(&stack), "{path}");
+        }
+
+        let alt = XmlPath::parse("section/para/@xml:lang").unwrap();
+        assert!(alt.selects_attribute(&stack, "xml:lang"));
+        assert!(!alt.selects_text(&stack));
+        assert!(
+            XmlPath::parse("@*")
+                .unwrap()
+                .selects_attribute(&stack, "id")
+        );
+
+        for malformed in [
+            "",
+            "//",
+            "para/",
+            "para[@role",
+            "para[role]",
+            "@alt/para",
+            "3d",
+        ] {
+            assert!(XmlPath::parse(malformed).is_none(), "{malformed}");
+        }
+    }
+
+    #[test]
+    fn test_scramble_xml() {
+        let s = "<?xml version=\"1.0\"?>\n<!DOCTYPE book [<!ENTITY co \"Company\">]>\n\
+                 <book xmlns=\"http://docbook.org/ns/docbook\" xmlns:xl=\"http://www.w3.org/1999/xlink\">\
+                 <title>Hello world</title><para>w<emphasis>or</emphasis>d caf&#233;s \
+                 <literal>kept</literal><![CDATA[raw <text>]]><!-- note --></para>\
+                 <mediaobject><imagedata alt=\"Alt text\" xl:href=\"a.png\"/></mediaobject></book>";
+
+        assert_eq!(
+            scramble_xml(s, &XmlOptions::new(), reverse),
+            s.replace("Hello world", "olleH dlrow").replace(
+                "w<emphasis>or</emphasis>d caf&#233;s",
+                "d<emphasis>ro</emphasis>w s&#233;fac"
+            )
+        );
+
+        let options = XmlOptions::new().with_paths([
+            XmlPath::parse("/book/para").unwrap(),
+            XmlPath::parse("//imagedata/@*").unwrap(),
+        ]);
+        assert_eq!(
+            scramble_xml(s, &options, reverse),
+            s.replace(
+                "w<emphasis>or</emphasis>d caf&#233;s",
+                "d<emphasis>ro</emphasis>w s&#233;fac"
+            )
+            .replace("Alt text", "tlA txet")
+            .replace("a.png", "gnp.a")
+        );
+
+        // HTML's named references are not predefined in XML
+        assert_eq!(
+            scramble_xml("<p>caf&eacute;s to&amp;go</p>", &XmlOptions::new(), reverse),
+            "<p>fac&eacute;s og&amp;ot</p>"
+        );
+    }
+}
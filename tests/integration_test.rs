@@ -1,4 +1,15 @@
-use typoglycemia::{typoglycemia, typoglycemia_leet};
+#![allow(clippy::get_first, clippy::iter_nth_zero, clippy::println_empty_string)]
+
+use typoglycemia::{
+    BigramTable, CasePolicy, Curve, DetectionScope, Difficulty, HangulMode, HtmlOptions,
+    Hyphenator, IndicMode, JsonPath, Language, Metric, Normalization, NumericPolicy, Ramp,
+    RampUnit, Scrambler, ShuffleMode, StructuredOptions, SyllableMode, Target, WordOrder,
+    XmlOptions, XmlPath, detect_language, measure, measure_text, typoglycemia, typoglycemia_html,
+    typoglycemia_html_with, typoglycemia_json, typoglycemia_json_with, typoglycemia_lang,
+    typoglycemia_leet, typoglycemia_leet_lang, typoglycemia_markdown, typoglycemia_markdown_with,
+    typoglycemia_with, typoglycemia_with_stats, typoglycemia_xml, typoglycemia_xml_with,
+    typoglycemia_yaml, typoglycemia_yaml_with,
+};
 use unicode_segmentation::UnicodeSegmentation;
 
 #[cfg(test)]
@@ -64,6 +75,748 @@ fn it_ignores_beginning_and_ending_non_ascii() {
     assert_eq!(g.get(3), Some(&"❤️"));
 }
 
+#[test]
+fn it_only_scrambles_selected_words() {
+    let input = "reading passages with graded difficulty";
+
+    let none = Scrambler::new().with_probability(0.0);
+    assert_eq!(typoglycemia_with(input, &none), input);
+
+    let glossary = Scrambler::new().with_target(Target::glossary(["passages"]));
+    let result = typoglycemia_with(input, &glossary);
+    let parts: Vec<&str> = result.split_whitespace().collect();
+    assert_eq!(parts[0], "reading");
+    assert_eq!(parts[2], "with");
+    assert_eq!(parts[3], "graded");
+    assert_eq!(parts[4], "difficulty");
+}
+
+#[test]
+fn it_is_reproducible_with_a_seed() {
+    let input = "Once upon a midnight dreary, while I pondered, weak and weary";
+    let scrambler = Scrambler::new().with_probability(0.5).with_seed(1986);
+
+    assert_eq!(
+        typoglycemia_with(input, &scrambler),
+        typoglycemia_with(input, &scrambler)
+    );
+}
+
+#[test]
+fn it_ramps_difficulty_across_paragraphs() {
+    let input = "Once upon a midnight dreary, while I pondered.\n\n\
+    Over many a quaint and curious volume.\n\n\
+    While I nodded, nearly napping, suddenly there came a tapping.";
+    let scrambler = Scrambler::new()
+        .with_ramp(Ramp::new(RampUnit::Paragraph).with_curve(Curve::EaseIn))
+        .with_seed(22);
+    let (result, stats) = typoglycemia_with_stats(input, &scrambler);
+
+    assert!(result.starts_with("Once upon a midnight dreary, while I pondered.\n\n"));
+    assert_eq!(result.matches("\n\n").count(), 2);
+    assert_eq!(stats.segments.len(), 3);
+    assert_eq!(stats.words, input.split_whitespace().count());
+    assert_eq!(stats.segments[0].scrambled, 0);
+    assert_eq!(stats.segments[1].rate, 0.25);
+    assert_eq!(stats.segments[2].intensity, 1.0);
+    assert!(matches!(stats.ramp.unwrap().curve(), Curve::EaseIn));
+}
+
+#[test]
+fn it_targets_a_difficulty_band() {
+    let input = "remarkable reading passages challenge curious students";
+    let scrambler =
+        Scrambler::new().with_difficulty(Difficulty::new(Metric::Displacement, 1.0, 10.0));
+    let result = typoglycemia_with(input, &scrambler);
+
+    for (original, scrambled) in input.split_whitespace().zip(result.split_whitespace()) {
+        let m = measure(original, scrambled, BigramTable::english());
+        assert!(m.displacement >= 1.0, "{original} => {scrambled}");
+    }
+}
+
+#[test]
+fn it_keeps_clusters_together() {
+    let input = "watching shoeshine northern quickest carrillo";
+    let scrambler = Scrambler::new().with_cluster_preservation(true);
+    let spanish = Scrambler::new()
+        .with_language(Language::Spanish)
+        .with_cluster_preservation(true);
+
+    for _ in 0..20 {
+        let result = typoglycemia_with(input, &scrambler);
+        for cluster in ["tch", "sh", "th", "qu", "ck"] {
+            assert!(result.matches(cluster).count() >= input.matches(cluster).count());
+        }
+
+        let result = typoglycemia_with("carrillo", &spanish);
+        assert!(result.contains("rr") && result.contains("ll"), "{result}");
+    }
+}
+
+#[test]
+fn it_keeps_affixes_intact() {
+    let english = Scrambler::new().with_affix_preservation(true);
+    let spanish = Scrambler::new()
+        .with_language(Language::Spanish)
+        .with_affix_preservation(true);
+
+    for _ in 0..20 {
+        let result = typoglycemia_with("Rethinking everything, unfortunately.", &english);
+        let words: Vec<&str> = result.split(' ').collect();
+        assert!(
+            words[0].starts_with("Re") && words[0].ends_with("ing"),
+            "{result}"
+        );
+        assert!(
+            words[2].starts_with("un") && words[2].ends_with("ly."),
+            "{result}"
+        );
+
+        let result = typoglycemia_with("organización", &spanish);
+        assert!(
+            result.starts_with('o') && result.ends_with("ción"),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_follows_language_profiles() {
+    for _ in 0..20 {
+        let result = typoglycemia_lang("Lorsqu'il arrive, dites-leur bonjour.", Language::French);
+        assert!(
+            result.starts_with("Lorsqu'il ") && result.contains("-leur "),
+            "{result}"
+        );
+
+        let result = typoglycemia_lang("Dá-lhes d'água", Language::Portuguese);
+        assert!(result.starts_with("Dá-lhes d'"), "{result}");
+
+        let result = typoglycemia_lang("jusqu’ici lorsqu’elles", Language::French);
+        assert!(
+            result.starts_with("jusqu’i") && result.contains(" lorsqu’e"),
+            "{result}"
+        );
+    }
+
+    let scrambler = Scrambler::new()
+        .with_language(Language::Dutch)
+        .with_stopword_preservation(true);
+    assert_eq!(
+        typoglycemia_with("hebben kunnen zich", &scrambler),
+        "hebben kunnen zich"
+    );
+
+    let result = typoglycemia_leet_lang("Straße", 1, Language::German);
+    assert!(result.starts_with('S') && result.contains('β'), "{result}");
+}
+
+#[test]
+fn it_detects_the_language_per_paragraph() {
+    let input = "Lorsqu'il pleut, nous restons à la maison avec les enfants.\n\n\
+                 When it rains, we stay at home with the children.";
+    assert_eq!(detect_language(input), Some(Language::French));
+
+    let scrambler = Scrambler::new()
+        .with_language(Language::Auto)
+        .with_detection_scope(DetectionScope::Paragraph)
+        .with_stopword_preservation(true);
+    for _ in 0..20 {
+        // French elisions and stopwords up top, English stopwords below
+        let result = typoglycemia_with(input, &scrambler);
+        assert!(result.starts_with("Lorsqu'il "), "{result}");
+        assert!(
+            result.contains(" avec ") && result.contains(" with "),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_normalizes_unicode() {
+    let nfd = "Cafe\u{301} cre\u{300}me bru\u{302}le\u{301}e";
+    let nfc = "Caf\u{e9} cr\u{e8}me br\u{fb}l\u{e9}e";
+
+    let composed = Scrambler::new().with_normalization(Normalization::Nfc);
+    let decomposed = Scrambler::new().with_normalization(Normalization::Nfd);
+    let preserved = Scrambler::new();
+    for _ in 0..20 {
+        let result = typoglycemia_with(nfd, &composed);
+        assert!(!result.contains('\u{301}') && result.contains('\u{e9}'));
+
+        let result = typoglycemia_with(nfc, &decomposed);
+        assert_eq!(result.chars().count(), nfd.chars().count());
+
+        // both forms keep the accented last letter of "Café" in place, in their own form
+        let result = typoglycemia_with(nfd, &preserved);
+        assert!(
+            result.starts_with('C') && result.contains("e\u{301} "),
+            "{result}"
+        );
+        let result = typoglycemia_with(nfc, &preserved);
+        assert!(
+            result.starts_with('C') && result.contains("\u{e9} "),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_handles_greek_and_cyrillic() {
+    for _ in 0..20 {
+        let result = typoglycemia("«Здравствуйте», (Καλημέρα)!");
+        assert!(
+            result.starts_with("«З") && result.contains("е»,"),
+            "{result}"
+        );
+        assert!(result.contains("(Κ") && result.ends_with("α)!"), "{result}");
+
+        // every level swaps the look-alikes for Latin letters
+        for level in 1..=3 {
+            let result = typoglycemia_leet("Москва", level);
+            assert!(result.starts_with('M') && !result.contains('М'), "{result}");
+        }
+        assert!(typoglycemia_leet("ΚΑΙΡΟΣ", 1).starts_with('K'));
+        assert_eq!(typoglycemia_leet("Я", 2), "R");
+    }
+}
+
+#[test]
+fn it_handles_right_to_left_scripts() {
+    let text = "\u{2067}«الكِتَابُ» مُفِيدٌ\u{2069}، שָׁלוֹם עוֹלָם!";
+    let letters = |s: &str| {
+        let mut g: Vec<String> = s.graphemes(true).map(String::from).collect();
+        g.sort_unstable();
+        g
+    };
+
+    for _ in 0..20 {
+        let result = typoglycemia(text);
+        assert_eq!(letters(&result), letters(text));
+        assert!(
+            result.starts_with("\u{2067}«ا") && result.contains("ُ» م"),
+            "{result}"
+        );
+        assert!(
+            result.contains("ٌ\u{2069}، שָׁ") && result.ends_with("ם!"),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_scrambles_korean() {
+    let text = "대한민국에서 한국어를 배웁니다.";
+    let jamo = Scrambler::new().with_hangul_mode(HangulMode::Jamo);
+
+    for _ in 0..20 {
+        let result = typoglycemia(text);
+        assert_eq!(result.chars().count(), text.chars().count());
+        assert!(
+            result.starts_with('대') && result.ends_with("다."),
+            "{result}"
+        );
+
+        // every block is still a precomposed syllable
+        let result = typoglycemia_with(text, &jamo);
+        assert_eq!(result.chars().count(), text.chars().count());
+        assert!(
+            result
+                .chars()
+                .all(|c| ('가'..='힣').contains(&c) || c == ' ' || c == '.'),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_segments_chinese_and_japanese() {
+    // the dictionary splits 研究|表明|汉字|的|顺序, each two-character word swaps
+    assert_eq!(typoglycemia("研究表明汉字的顺序。"), "究研明表字汉的序顺。");
+
+    let text = "私は東京大学で日本語を勉強しています。";
+    let scrambler = Scrambler::new().with_target(Target::glossary(["日本語"]));
+    for _ in 0..20 {
+        let result = typoglycemia_with(text, &scrambler);
+        assert!(
+            result.starts_with("私は東京大学で") && result.ends_with("を勉強しています。"),
+            "{result}"
+        );
+        assert!(
+            result.contains("本日語") || result.contains("日語本"),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_segments_thai_lao_and_khmer() {
+    for (text, words) in [
+        ("ผมชอบกินข้าวเหนียว", 5),
+        ("ສະບາຍດີຂອບໃຈ", 2),
+        ("ខ្ញុំនិយាយភាសាខ្មែរ", 4),
+    ] {
+        for _ in 0..20 {
+            let (result, stats) = typoglycemia_with_stats(text, &Scrambler::new());
+            assert_eq!(stats.words, words);
+            assert!(result.starts_with(text.chars().next().unwrap()), "{result}");
+            assert!(result.ends_with(text.chars().last().unwrap()), "{result}");
+        }
+    }
+
+    // the preposed vowels travel with their consonants
+    for _ in 0..20 {
+        let result = typoglycemia("ข้าวเหนียวแน่นๆ");
+        assert!(result.contains("เห") && result.contains("แน"), "{result}");
+    }
+}
+
+#[test]
+fn it_scrambles_indic_aksharas() {
+    let scrambler = Scrambler::new().with_indic_mode(IndicMode::Aksharas);
+    assert_eq!(scrambler.indic_mode(), IndicMode::Aksharas);
+    assert_eq!(Scrambler::new().indic_mode(), IndicMode::Graphemes);
+
+    for _ in 0..20 {
+        let result = typoglycemia_with("க்ஷேத்ரபாலகம் विद्यार्थियों", &scrambler);
+        assert!(result.starts_with("க்ஷே"), "{result}");
+        assert!(result.contains("த்ர") && result.contains("द्या"), "{result}");
+        assert!(result.ends_with("यों"), "{result}");
+    }
+}
+
+#[test]
+fn it_applies_numeric_policies() {
+    let text = "Version v1.2.3 costs $100 on 2024-01-15, call +1-555-123-4567 or 0xFF.";
+    let recognize = Scrambler::new().with_numeric_policy(NumericPolicy::Recognize);
+    assert_eq!(recognize.numeric_policy(), NumericPolicy::Recognize);
+    assert_eq!(Scrambler::new().numeric_policy(), NumericPolicy::Leading);
+
+    for _ in 0..20 {
+        let result = typoglycemia_with(text, &recognize);
+        for kept in ["v1.2.3", "$100", "2024-01-15,", "+1-555-123-4567", "0xFF."] {
+            assert!(result.split(' ').any(|w| w == kept), "{result}");
+        }
+
+        let result = typoglycemia_with(
+            "Windows10Professional",
+            &Scrambler::new().with_numeric_policy(NumericPolicy::ScrambleLetters),
+        );
+        assert!(result.starts_with('W') && result.ends_with('l'), "{result}");
+        assert_eq!(&result[7..9], "10", "{result}");
+    }
+}
+
+#[test]
+fn it_keeps_case_patterns() {
+    let positions = Scrambler::new().with_case_policy(CasePolicy::Positions);
+    assert_eq!(positions.case_policy(), CasePolicy::Positions);
+    assert_eq!(Scrambler::new().case_policy(), CasePolicy::Letters);
+
+    let text = "McDonald sells iPhone cases, LaTeX guides and JavaScript books";
+    let pattern = |s: &str| -> Vec<bool> { s.chars().map(char::is_uppercase).collect() };
+    for _ in 0..20 {
+        let result = typoglycemia_with(text, &positions);
+        assert_eq!(pattern(&result), pattern(text), "{result}");
+
+        let result = typoglycemia_with(
+            "The NASA and HTML5 FAQs are useful",
+            &Scrambler::new().with_acronym_preservation(true),
+        );
+        assert!(result.contains("NASA and HTML5 FAQs"), "{result}");
+    }
+}
+
+#[test]
+fn it_scrambles_subwords() {
+    let scrambler = Scrambler::new().with_subword_splitting(true);
+    assert!(scrambler.subword_splitting());
+    assert!(!Scrambler::new().subword_splitting());
+
+    let text = "Call getUserName with max_retry_count #ThrowbackThursday";
+    let shape = |s: &str| -> String {
+        s.chars()
+            .map(|c| match c {
+                'A'..='Z' => 'A',
+                'a'..='z' => 'a',
+                _ => c,
+            })
+            .collect()
+    };
+    for _ in 0..20 {
+        let result = typoglycemia_with(text, &scrambler);
+        assert_eq!(shape(&result), shape(text), "{result}");
+        let words: Vec<&str> = result.split(' ').collect();
+        assert!(
+            words[1].starts_with("getU") && words[1].contains("rN"),
+            "{result}"
+        );
+        assert!(
+            words[3].starts_with("max_r") && words[3].contains("y_c"),
+            "{result}"
+        );
+        assert!(
+            words[4].starts_with("#T") && words[4].contains("kT"),
+            "{result}"
+        );
+    }
+}
+
+#[test]
+fn it_preserves_named_entities() {
+    let text = "San Francisco sits north of Palo Alto. Yesterday, Margaret visited \
+                New York with Zorblax Industries.";
+    let scrambler = Scrambler::new()
+        .with_entity_preservation(true)
+        .with_gazetteer(["Zorblax Industries"]);
+
+    let mut moved: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_with(text, &scrambler);
+        for name in [
+            "San Francisco ",
+            " Palo Alto. ",
+            " Margaret ",
+            " New York ",
+            " Zorblax Industries.",
+        ] {
+            assert!(result.contains(name), "{result}");
+        }
+        moved |= !result.contains("Yesterday");
+    }
+    assert!(moved);
+
+    // nouns are capitalized in German, names only survive through the gazetteer
+    let scrambler = Scrambler::new()
+        .with_language(Language::German)
+        .with_gazetteer(["Hamburg"]);
+    for _ in 0..20 {
+        let result = typoglycemia_with("Wir fahren morgen nach Hamburg", &scrambler);
+        assert!(result.ends_with(" Hamburg"), "{result}");
+    }
+}
+
+#[test]
+fn it_shuffles_word_order() {
+    let text = "Reading words in any order is harder. Still, most readers manage it fine!";
+    let reorder = Scrambler::new()
+        .with_word_order(WordOrder::Shuffle)
+        .with_probability(0.0);
+    assert_eq!(reorder.word_order(), WordOrder::Shuffle);
+    assert_eq!(Scrambler::new().word_order(), WordOrder::Keep);
+
+    let sorted = |s: &str| -> Vec<String> {
+        let mut words: Vec<String> = s
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(String::from)
+            .collect();
+        words.sort_unstable();
+        words
+    };
+    let mut moved: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_with(text, &reorder);
+        assert_eq!(sorted(&result), sorted(text));
+        assert!(result.starts_with("Reading "), "{result}");
+        assert!(result.contains(" harder. Still, "), "{result}");
+        assert!(result.ends_with(" fine!"), "{result}");
+        moved |= result != text;
+
+        // combined with word-level scrambling
+        let result = typoglycemia_with(text, &Scrambler::new().with_word_order(WordOrder::Shuffle));
+        assert!(
+            result.starts_with('R') && result.ends_with("e!"),
+            "{result}"
+        );
+    }
+    assert!(moved);
+
+    let seeded = Scrambler::new()
+        .with_word_order(WordOrder::Shuffle)
+        .with_seed(7);
+    assert_eq!(
+        typoglycemia_with(text, &seeded),
+        typoglycemia_with(text, &seeded)
+    );
+}
+
+#[test]
+fn it_scrambles_markdown_prose() {
+    let markdown = "---\ntitle: Markdown support\ntags: [typoglycemia]\n---\n\n\
+                    ## Installation instructions {#install}\n\n\
+                    Run `cargo install typoglycemia` and read the **excellent** \
+                    [documentation](https://docs.rs/typoglycemia \"Reference\") today.\n\n\
+                    ```shell\ntypoglycemia --help\n```\n\n\
+                    | Feature   | Status    |\n|:----------|----------:|\n| Markdown  | supported |\n\n\
+                    - [x] Scramble paragraphs, visit https://example.com/getting-started\n\
+                    - [ ] <span class=\"note\">Inline HTML</span> stays\n";
+
+    let mut changed: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_markdown(markdown);
+        assert_eq!(result.len(), markdown.len());
+        for kept in [
+            "---\ntitle: Markdown support\ntags: [typoglycemia]\n---\n\n## I",
+            " {#install}\n\nRun `cargo install typoglycemia` and ",
+            "**e",
+            "](https://docs.rs/typoglycemia \"Reference\") ",
+            "```shell\ntypoglycemia --help\n```",
+            "| F",
+            "|\n|:----------|----------:|\n| M",
+            "- [x] S",
+            " https://example.com/getting-started\n- [ ] <span class=\"note\">I",
+            "</span> ",
+        ] {
+            assert!(result.contains(kept), "{kept}\n{result}");
+        }
+        changed |= result != markdown;
+    }
+    assert!(changed);
+
+    let seeded = Scrambler::new().with_seed(3);
+    assert_eq!(
+        typoglycemia_markdown_with(markdown, &seeded),
+        typoglycemia_markdown_with(markdown, &seeded)
+    );
+}
+
+#[test]
+fn it_scrambles_html_text() {
+    let html = "<!DOCTYPE html>\n<html><head><title>Page title</title>\
+                <style>p { color: black; }</style></head>\n\
+                <body><!-- generated comment -->\n\
+                <p class=\"intro\" title=\"Helpful tooltip\">Typoglycemia <b>keeps</b> \
+                re<em>adab</em>ility surprisingly&nbsp;intact</p>\n\
+                <pre>preformatted content</pre><code>computer code</code>\n\
+                <script>if (letters < 10) { alert(\"scrambled\"); }</script>\n\
+                <img src=\"picture.png\" alt=\"Descriptive caption\"></body></html>";
+
+    let mut changed: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_html(html);
+        assert_eq!(result.len(), html.len());
+        for kept in [
+            "<!DOCTYPE html>\n<html><head><title>Page title</title><style>p { color: black; }</style></head>",
+            "<body><!-- generated comment -->\n<p class=\"intro\" title=\"Helpful tooltip\">T",
+            "a <b>k",
+            "s</b> r",
+            "surprisingly&nbsp;intact",
+            "t</p>\n<pre>preformatted content</pre><code>computer code</code>\n",
+            "<script>if (letters < 10) { alert(\"scrambled\"); }</script>",
+            "<img src=\"picture.png\" alt=\"Descriptive caption\"></body></html>",
+        ] {
+            assert!(result.contains(kept), "{kept}\n{result}");
+        }
+
+        // the word split across <em> keeps its letters and its first and last letters
+        let start = result.find("</b> ").unwrap() + 5;
+        let end = result[start..].find(' ').unwrap() + start;
+        let word: String = result[start..end].replace("<em>", "").replace("</em>", "");
+        let mut letters: Vec<char> = word.chars().collect();
+        letters.sort_unstable();
+        let mut expected: Vec<char> = "readability".chars().collect();
+        expected.sort_unstable();
+        assert_eq!(letters, expected, "{result}");
+        assert!(word.starts_with('r') && word.ends_with('y'), "{result}");
+        assert_eq!(result[start..end].find("<em>"), Some(2), "{result}");
+        assert_eq!(result[start..end].find("</em>"), Some(10), "{result}");
+        changed |= result != html;
+    }
+    assert!(changed);
+
+    // words containing character references are copied unless entities are enabled
+    let referenced = "<p>w<b>or</b>d&amp;ing caf&eacute;s</p>";
+    assert_eq!(typoglycemia_html(referenced), referenced);
+
+    // attributes are scrambled when configured, skipped elements can be replaced
+    let options = HtmlOptions::new()
+        .with_attributes(["alt"])
+        .with_skipped_elements(["script"]);
+    let seeded = Scrambler::new().with_seed(11);
+    let result = typoglycemia_html_with(html, &seeded, &options);
+    assert_eq!(result, typoglycemia_html_with(html, &seeded, &options));
+    assert!(result.contains("title=\"Helpful tooltip\""));
+    assert!(result.contains("alt=\"D"));
+    assert!(!result.contains("Descriptive caption"));
+    assert!(!result.contains("<code>computer code</code>"), "{result}");
+    assert!(
+        !result.contains("<pre>preformatted content</pre>"),
+        "{result}"
+    );
+}
+
+#[test]
+fn it_scrambles_xml_text() {
+    let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+               <?xml-model href=\"docbook.rng\"?>\n\
+               <!DOCTYPE article [<!ENTITY product \"Typoglycemia\">]>\n\
+               <article xmlns=\"http://docbook.org/ns/docbook\" xmlns:xl=\"http://www.w3.org/1999/xlink\" version=\"5.0\">\n\
+               <title>Reading scrambled words</title>\n\
+               <!-- reviewed comment -->\n\
+               <section xml:id=\"intro\" role=\"overview\">\n\
+               <para>Readers understand <emphasis>surprisingly</emphasis> jumbled sentences.</para>\n\
+               <programlisting><![CDATA[let letters = \"unchanged\";]]></programlisting>\n\
+               <para>Run <command>cargo install</command> first.</para>\n\
+               <mediaobject><imageobject><imagedata fileref=\"figure.png\" xl:title=\"Figure caption\"/></imageobject></mediaobject>\n\
+               </section>\n</article>";
+
+    let kept = [
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<?xml-model href=\"docbook.rng\"?>\n\
+         <!DOCTYPE article [<!ENTITY product \"Typoglycemia\">]>\n\
+         <article xmlns=\"http://docbook.org/ns/docbook\" xmlns:xl=\"http://www.w3.org/1999/xlink\" version=\"5.0\">\n<title>R",
+        "</title>\n<!-- reviewed comment -->\n<section xml:id=\"intro\" role=\"overview\">\n<para>R",
+        "d <emphasis>s",
+        "y</emphasis> j",
+        ".</para>\n<programlisting><![CDATA[let letters = \"unchanged\";]]></programlisting>\n\
+         <para>Run <command>cargo install</command> f",
+        "t.</para>\n<mediaobject><imageobject><imagedata fileref=\"figure.png\" xl:title=\"",
+        "\"/></imageobject></mediaobject>\n</section>\n</article>",
+    ];
+    let mut changed: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_xml(xml);
+        assert_eq!(result.len(), xml.len());
+        for kept in kept {
+            assert!(result.contains(kept), "{kept}\n{result}");
+        }
+        assert!(result.contains("xl:title=\"Figure caption\""));
+        changed |= result != xml;
+    }
+    assert!(changed);
+
+    // only the selected elements and attributes are scrambled
+    let options = XmlOptions::new().with_paths([
+        XmlPath::parse("//section[@role='overview']/para").unwrap(),
+        XmlPath::parse("//imagedata/@xl:title").unwrap(),
+    ]);
+    let seeded = Scrambler::new().with_seed(5);
+    let result = typoglycemia_xml_with(xml, &seeded, &options);
+    assert_eq!(result, typoglycemia_xml_with(xml, &seeded, &options));
+    assert!(result.contains("<title>Reading scrambled words</title>"));
+    assert!(!result.contains("understand <emphasis>surprisingly</emphasis> jumbled sentences"));
+    assert!(!result.contains("Figure caption"));
+    assert!(result.contains("fileref=\"figure.png\" xl:title=\"F"));
+    for kept in kept {
+        assert!(result.contains(kept), "{kept}\n{result}");
+    }
+}
+
+#[test]
+fn it_scrambles_structured_strings() {
+    let json = "{\n  \"fixtures\": [\n    {\"id\": \"user-profile\", \"title\": \"Customer information\", \"retries\": 3},\n    \
+                {\"id\": \"order-history\", \"title\": \"Previous purchases\", \"enabled\": false}\n  ],\n  \
+                \"message\": \"Everything \\\"worked\\\" perfectly\",\n  \"ratio\": 1.5e3\n}\n";
+
+    let mut changed: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_json(json);
+        assert_eq!(result.len(), json.len());
+        for kept in [
+            "{\n  \"fixtures\": [\n    {\"id\": \"u",
+            "\", \"title\": \"C",
+            "n\", \"retries\": 3},\n    {\"id\": \"o",
+            "s\", \"enabled\": false}\n  ],\n  \"message\": \"E",
+            "g \\\"w",
+            "d\\\" p",
+            "y\",\n  \"ratio\": 1.5e3\n}\n",
+        ] {
+            assert!(result.contains(kept), "{kept}\n{result}");
+        }
+        changed |= result != json;
+    }
+    assert!(changed);
+
+    // only the selected values are scrambled
+    let options = StructuredOptions::new()
+        .with_paths([JsonPath::parse("$.fixtures[*].title").unwrap()])
+        .with_keys(["message"]);
+    let seeded = Scrambler::new().with_seed(8);
+    let result = typoglycemia_json_with(json, &seeded, &options);
+    assert_eq!(result, typoglycemia_json_with(json, &seeded, &options));
+    assert!(result.contains("\"id\": \"user-profile\""));
+    assert!(result.contains("\"id\": \"order-history\""));
+    assert!(!result.contains("Customer information"));
+
+    let yaml = "# fixtures\nfixtures:\n  - id: user-profile\n    title: Customer information  # shown\n    retries: 3\n  \
+                - id: order-history\n    title: 'Previous purchases'\n    enabled: false\n\
+                message: |\n  Everything worked\n  perfectly\nratio: 1.5\n";
+    let mut changed: bool = false;
+    for _ in 0..20 {
+        let result = typoglycemia_yaml(yaml);
+        assert_eq!(result.len(), yaml.len());
+        for kept in [
+            "# fixtures\nfixtures:\n  - id: u",
+            "e\n    title: C",
+            "n  # shown\n    retries: 3\n  - id: o",
+            "y\n    title: 'P",
+            "s'\n    enabled: false\nmessage: |\n  E",
+            "g w",
+            "d\n  p",
+            "y\nratio: 1.5\n",
+        ] {
+            assert!(result.contains(kept), "{kept}\n{result}");
+        }
+        changed |= result != yaml;
+    }
+    assert!(changed);
+
+    let result = typoglycemia_yaml_with(yaml, &seeded, &options);
+    assert_eq!(result, typoglycemia_yaml_with(yaml, &seeded, &options));
+    assert!(result.contains("id: user-profile\n"));
+    assert!(result.contains("id: order-history\n"));
+    assert!(!result.contains("Previous purchases"));
+}
+
+#[test]
+fn it_keeps_marks_on_punctuation() {
+    assert_eq!(typoglycemia(";\u{e49}"), ";\u{e49}");
+    assert_eq!(typoglycemia("\"\u{17d2}"), "\"\u{17d2}");
+    assert_eq!(
+        typoglycemia_html("<p>x ;\u{e49} y</p>"),
+        "<p>x ;\u{e49} y</p>"
+    );
+}
+
+#[test]
+fn it_rejoins_words_with_single_spaces() {
+    assert_eq!(typoglycemia("  the\n\nend  of\tit "), "the end of it");
+    assert_eq!(
+        typoglycemia_lang(" jusqu'ici\n", Language::French),
+        "jusqu'ici"
+    );
+    assert_eq!(
+        typoglycemia_with("  the\n\nend ", &Scrambler::new()),
+        "  the\n\nend "
+    );
+}
+
+#[test]
+fn it_scrambles_within_syllables() {
+    let hyphenator = Hyphenator::parse("\\hyphenation{ wa-ter-mel-on }");
+    assert_eq!(
+        hyphenator.syllables("Watermelon"),
+        vec!["Wa", "ter", "mel", "on"]
+    );
+
+    let scrambler = Scrambler::new()
+        .with_syllable_mode(SyllableMode::Whole)
+        .with_hyphenator(hyphenator)
+        .with_seed(3);
+    let result = typoglycemia_with("Watermelon!", &scrambler);
+    assert!(["Watermelon!", "Wamelteron!"].contains(&result.as_str()));
+
+    let german = Scrambler::new()
+        .with_language(Language::German)
+        .with_syllable_mode(SyllableMode::Within);
+    for _ in 0..20 {
+        let result = typoglycemia_with("Silbentrennung", &german);
+        assert!(result.starts_with('S') && result.ends_with('g'));
+        assert_eq!(result.len(), "Silbentrennung".len());
+    }
+}
+
 #[test]
 /**
  * Example output, The Raven by E.A. Poe (English)
@@ -176,3 +929,40 @@ fn typoglycemia_leet_test() {
     println!("{}", result);
     assert_eq!(1, 1);
 }
+
+#[test]
+/**
+ * Readable vs. hard shuffle modes (German)
+ * $cargo test -- --show-output
+ */
+fn readable_and_hard_modes_german() {
+    let input = "Einst in einer Mittnacht schaurig, als ich in entschwundner Kunde wunderlicher \
+    Bücher forschte, bis mein Geist die Kraft verlor";
+    let table = BigramTable::for_language(Language::German);
+
+    let readable = Scrambler::new()
+        .with_language(Language::German)
+        .with_shuffle_mode(ShuffleMode::Readable);
+    let hard = Scrambler::new()
+        .with_language(Language::German)
+        .with_shuffle_mode(ShuffleMode::Hard);
+
+    let readable_result = typoglycemia_with(input, &readable);
+    let hard_result = typoglycemia_with(input, &hard);
+
+    println!();
+    println!("{}", "*".repeat(40));
+    println!("Integration test example ouput: readable_and_hard_modes_german()");
+    println!("{}", "*".repeat(40));
+    println!("Original:\n");
+    println!("{}", input);
+    println!("\nReadable:\n");
+    println!("{}", readable_result);
+    println!("\nHard:\n");
+    println!("{}", hard_result);
+
+    assert!(
+        measure_text(input, &readable_result, table).illegal_bigrams
+            < measure_text(input, &hard_result, table).illegal_bigrams
+    );
+}